@@ -23,6 +23,8 @@ use mpesa_rust_sdk::models::models::{
     CustomerToBusinessPaymentResultData, Item, ItemDetails, MixedTypeValue,
     RegisterUrlInputDetails, RegisterUrlResponseData, ValidationResponseData,
 };
+use mpesa_rust_sdk::environment::environment::Environment;
+use mpesa_rust_sdk::error::error::MpesaError;
 use mpesa_rust_sdk::MpesaGateway;
 use mysql::*;
 use serde::{Deserialize, Serialize};
@@ -46,14 +48,14 @@ pub(crate) async fn register_client_urls(data: web::Data<Pool>) -> impl Responde
         let consumer_key: String = get_settings_details(&data, String::from("consumerkeympesa"));
         let consumer_secret: String =
             get_settings_details(&data, String::from("consumersecretmpesa"));
-        let auth_token_url: String = get_settings_details(&data, String::from("authtokenurlmpesa"));
+        let environment: Environment = get_environment(&data);
 
-        let _result = MpesaGateway::new(consumer_key, consumer_secret, auth_token_url);
+        let _result = MpesaGateway::new(consumer_key, consumer_secret, environment);
         if let Ok(mpesa_gateway) = _result {
             // Initiate the request through the sdk
             let _output = mpesa_gateway.register_url(register_url_details);
 
-            let _result: std::result::Result<RegisterUrlResponseData, String> = _output.await;
+            let _result: std::result::Result<RegisterUrlResponseData, MpesaError> = _output.await;
 
             if let Ok(register_url_response_data) = _result {
                 println!(
@@ -73,7 +75,7 @@ pub(crate) async fn register_client_urls(data: web::Data<Pool>) -> impl Responde
 pub(crate) async fn process_b2c(data: web::Data<Pool>) -> impl Responder {
     let consumer_key: String = get_settings_details(&data, String::from("consumerkeympesa"));
     let consumer_secret: String = get_settings_details(&data, String::from("consumersecretmpesa"));
-    let auth_token_url: String = get_settings_details(&data, String::from("authtokenurlmpesa"));
+    let environment: Environment = get_environment(&data);
 
     let mobile_no = String::from("2547***");
     let amount_paid: u32 = 1500;
@@ -91,7 +93,7 @@ pub(crate) async fn process_b2c(data: web::Data<Pool>) -> impl Responder {
     );
 
     if let Ok(business_to_customer_data) = _result {
-        let _result = MpesaGateway::new(consumer_key, consumer_secret, auth_token_url);
+        let _result = MpesaGateway::new(consumer_key, consumer_secret, environment);
         if let Ok(mpesa_gateway) = _result {
             // Initiate the request through the sdk
             let _output = mpesa_gateway.b2c(business_to_customer_data);
@@ -101,7 +103,7 @@ pub(crate) async fn process_b2c(data: web::Data<Pool>) -> impl Responder {
                     Option<BusinessToCustomerResponseData>,
                     Option<BusinessToCustomerErrorResponseData>,
                 ),
-                String,
+                MpesaError,
             > = _output.await;
 
             match _result {
@@ -139,13 +141,10 @@ pub(crate) async fn process_b2c(data: web::Data<Pool>) -> impl Responder {
 pub(crate) async fn process_c2b_payment(data: web::Data<Pool>) -> impl Responder {
     let consumer_key: String = get_settings_details(&data, String::from("consumerkeympesa"));
     let consumer_secret: String = get_settings_details(&data, String::from("consumersecretmpesa"));
-    let auth_token_url: String = get_settings_details(&data, String::from("authtokenurlmpesa"));
-    let stk_push_url: String =
-        String::from("https://sandbox.safaricom.co.ke/mpesa/stkpush/v1/processrequest");
+    let environment: Environment = get_environment(&data);
     let business_short_code: String = String::from("17***");
     let pass_key: String =
         String::from("***");
-    let time_stamp: String = Local::now().format("%Y%m%d%H%M%S").to_string(); //"YYYYMMDDHHmmss";
     let transaction_type: String = String::from("CustomerPayBillOnline");
     let _amount: u32 = 1;
     let party_a: u64 = 2547***;
@@ -155,18 +154,11 @@ pub(crate) async fn process_c2b_payment(data: web::Data<Pool>) -> impl Responder
     let account_reference: String = String::from("Company X");
     let transaction_desc: String = String::from("Payment of X");
 
-    // _password = Shortcode+Passkey+Timestamp)
-    let short_code = &business_short_code;
-    let mut _password: String = short_code.to_string();
-    _password.push_str(&pass_key);
-    _password.push_str(&time_stamp);
-    let encoded_password = general_purpose::STANDARD.encode(_password);
-
-    let _result = CustomerToBusinessPaymentInputDetails::new(
-        stk_push_url,
+    // The time stamp (Africa/Nairobi time) and the password are generated from the pass key
+    let _result = CustomerToBusinessPaymentInputDetails::new_with_pass_key(
+        None,
         business_short_code,
-        encoded_password,
-        time_stamp,
+        pass_key,
         transaction_type,
         _amount,
         party_a,
@@ -178,7 +170,7 @@ pub(crate) async fn process_c2b_payment(data: web::Data<Pool>) -> impl Responder
     );
 
     if let Ok(customer_to_business_details) = _result {
-        let _result = MpesaGateway::new(consumer_key, consumer_secret, auth_token_url);
+        let _result = MpesaGateway::new(consumer_key, consumer_secret, environment);
         if let Ok(mpesa_gateway) = _result {
             // Initiate the request through the sdk
             let _output = mpesa_gateway.c2b_payment(customer_to_business_details);
@@ -187,7 +179,7 @@ pub(crate) async fn process_c2b_payment(data: web::Data<Pool>) -> impl Responder
                     Option<CustomerToBusinessPaymentResponseData>,
                     Option<CustomerToBusinessPaymentErrorResponseData>,
                 ),
-                String,
+                MpesaError,
             > = _output.await;
 
             match _result {
@@ -225,10 +217,8 @@ pub(crate) async fn process_c2b_payment(data: web::Data<Pool>) -> impl Responder
 pub(crate) async fn process_business_paybill(data: web::Data<Pool>) -> impl Responder {
     let consumer_key: String = get_settings_details(&data, String::from("consumerkeympesa"));
     let consumer_secret: String = get_settings_details(&data, String::from("consumersecretmpesa"));
-    let auth_token_url: String = get_settings_details(&data, String::from("authtokenurlmpesa"));
+    let environment: Environment = get_environment(&data);
 
-    let b2b_payment_request_url: String =
-        String::from("https://sandbox.safaricom.co.ke/mpesa/b2b/v1/paymentrequest");
     let _initiator: String = String::from("***");
     let security_credential: String = String::from("***");
     let command_id: String = String::from("BusinessPayBill");
@@ -244,7 +234,7 @@ pub(crate) async fn process_business_paybill(data: web::Data<Pool>) -> impl Resp
     let result_url: String = String::from("https://mydomain.com/b2b/result/");
 
     let _result = BusinessPayBillInputDetails::new(
-        b2b_payment_request_url,
+        None,
         _initiator,
         security_credential,
         command_id,
@@ -261,7 +251,7 @@ pub(crate) async fn process_business_paybill(data: web::Data<Pool>) -> impl Resp
     );
 
     if let Ok(business_paybill_details) = _result {
-        let _result = MpesaGateway::new(consumer_key, consumer_secret, auth_token_url);
+        let _result = MpesaGateway::new(consumer_key, consumer_secret, environment);
 
         if let Ok(mpesa_gateway) = _result {
             let _output = mpesa_gateway.business_paybill(business_paybill_details);
@@ -271,7 +261,7 @@ pub(crate) async fn process_business_paybill(data: web::Data<Pool>) -> impl Resp
                     Option<BusinessPayBillResponseData>,
                     Option<BusinessPayBillErrorResponseData>,
                 ),
-                String,
+                MpesaError,
             > = _output.await;
 
             match _result {
@@ -304,10 +294,8 @@ pub(crate) async fn process_business_paybill(data: web::Data<Pool>) -> impl Resp
 pub(crate) async fn process_business_buy_goods(data: web::Data<Pool>) -> impl Responder {
     let consumer_key: String = get_settings_details(&data, String::from("consumerkeympesa"));
     let consumer_secret: String = get_settings_details(&data, String::from("consumersecretmpesa"));
-    let auth_token_url: String = get_settings_details(&data, String::from("authtokenurlmpesa"));
+    let environment: Environment = get_environment(&data);
 
-    let b2b_payment_request_url: String =
-        String::from("https://sandbox.safaricom.co.ke/mpesa/b2b/v1/paymentrequest");
     let _initiator: String = String::from("***");
     let security_credential: String = String::from("***");
     let command_id: String = String::from("BusinessBuyGoods");
@@ -323,7 +311,7 @@ pub(crate) async fn process_business_buy_goods(data: web::Data<Pool>) -> impl Re
     let result_url: String = String::from("https://mydomain.com/b2b/result/");
 
     let _result = BusinessBuyGoodsInputDetails::new(
-        b2b_payment_request_url,
+        None,
         _initiator,
         security_credential,
         command_id,
@@ -340,7 +328,7 @@ pub(crate) async fn process_business_buy_goods(data: web::Data<Pool>) -> impl Re
     );
 
     if let Ok(business_buy_goods_details) = _result {
-        let _result = MpesaGateway::new(consumer_key, consumer_secret, auth_token_url);
+        let _result = MpesaGateway::new(consumer_key, consumer_secret, environment);
         if let Ok(mpesa_gateway) = _result {
             let _output = mpesa_gateway.business_buy_goods(business_buy_goods_details);
 
@@ -349,7 +337,7 @@ pub(crate) async fn process_business_buy_goods(data: web::Data<Pool>) -> impl Re
                     Option<BusinessBuyGoodsResponseData>,
                     Option<BusinessBuyGoodsErrorResponseData>,
                 ),
-                String,
+                MpesaError,
             > = _output.await;
 
             match _result {
@@ -422,9 +410,9 @@ pub(crate) async fn get_b2c_result(
 
     let consumer_key: String = get_settings_details(&data, String::from("consumerkeympesa"));
     let consumer_secret: String = get_settings_details(&data, String::from("consumersecretmpesa"));
-    let auth_token_url: String = get_settings_details(&data, String::from("authtokenurlmpesa"));
+    let environment: Environment = get_environment(&data);
 
-    let _result = MpesaGateway::new(consumer_key, consumer_secret, auth_token_url);
+    let _result = MpesaGateway::new(consumer_key, consumer_secret, environment);
     if let Ok(mpesa_gateway) = _result {
         let b2c_result_parameters_output_details =
             mpesa_gateway.get_b2c_result_parameters_output_details(result_parameters);
@@ -487,9 +475,9 @@ pub(crate) async fn get_c2bpayment_result(
 
     let consumer_key: String = get_settings_details(&data, String::from("consumerkeympesa"));
     let consumer_secret: String = get_settings_details(&data, String::from("consumersecretmpesa"));
-    let auth_token_url: String = get_settings_details(&data, String::from("authtokenurlmpesa"));
+    let environment: Environment = get_environment(&data);
 
-    let _result = MpesaGateway::new(consumer_key, consumer_secret, auth_token_url);
+    let _result = MpesaGateway::new(consumer_key, consumer_secret, environment);
     if let Ok(mpesa_gateway) = _result {
         let c2b_payment_result_parameters_output_details =
             mpesa_gateway.get_c2b_payment_result_parameters_output_details(list_of_items);
@@ -523,9 +511,9 @@ pub(crate) async fn get_business_paybill_result(
 
     let consumer_key: String = get_settings_details(&data, String::from("consumerkeympesa"));
     let consumer_secret: String = get_settings_details(&data, String::from("consumersecretmpesa"));
-    let auth_token_url: String = get_settings_details(&data, String::from("authtokenurlmpesa"));
+    let environment: Environment = get_environment(&data);
 
-    let _result = MpesaGateway::new(consumer_key, consumer_secret, auth_token_url);
+    let _result = MpesaGateway::new(consumer_key, consumer_secret, environment);
     if let Ok(mpesa_gateway) = _result {
         let business_paybill_result_parameters_output_details =
             mpesa_gateway.get_business_paybill_result_parameters_output_details(result_parameters);
@@ -567,9 +555,9 @@ pub(crate) async fn get_business_buy_goods_result(
 
     let consumer_key: String = get_settings_details(&data, String::from("consumerkeympesa"));
     let consumer_secret: String = get_settings_details(&data, String::from("consumersecretmpesa"));
-    let auth_token_url: String = get_settings_details(&data, String::from("authtokenurlmpesa"));
+    let environment: Environment = get_environment(&data);
 
-    let _result = MpesaGateway::new(consumer_key, consumer_secret, auth_token_url);
+    let _result = MpesaGateway::new(consumer_key, consumer_secret, environment);
     if let Ok(mpesa_gateway) = _result {
         let business_buy_goods_result_parameters_output_details = mpesa_gateway
             .get_business_buy_goods_result_parameters_output_details(result_parameters);
@@ -611,9 +599,9 @@ pub(crate) async fn get_business_paybill_timeout(
 
     let consumer_key: String = get_settings_details(&data, String::from("consumerkeympesa"));
     let consumer_secret: String = get_settings_details(&data, String::from("consumersecretmpesa"));
-    let auth_token_url: String = get_settings_details(&data, String::from("authtokenurlmpesa"));
+    let environment: Environment = get_environment(&data);
 
-    let _result = MpesaGateway::new(consumer_key, consumer_secret, auth_token_url);
+    let _result = MpesaGateway::new(consumer_key, consumer_secret, environment);
     if let Ok(mpesa_gateway) = _result {
         let business_paybill_timeout_parameters_output_details = mpesa_gateway
             .get_business_paybill_timeout_parameters_output_details(
@@ -650,9 +638,9 @@ pub(crate) async fn get_business_buy_goods_timeout(
 
     let consumer_key: String = get_settings_details(&data, String::from("consumerkeympesa"));
     let consumer_secret: String = get_settings_details(&data, String::from("consumersecretmpesa"));
-    let auth_token_url: String = get_settings_details(&data, String::from("authtokenurlmpesa"));
+    let environment: Environment = get_environment(&data);
 
-    let _result = MpesaGateway::new(consumer_key, consumer_secret, auth_token_url);
+    let _result = MpesaGateway::new(consumer_key, consumer_secret, environment);
     if let Ok(mpesa_gateway) = _result {
         let business_buy_goods_timeout_parameters_output_details = mpesa_gateway
             .get_business_buy_goods_timeout_parameters_output_details(
@@ -690,9 +678,9 @@ pub(crate) async fn validation_c2b(
     let org_account_balance = &validation_data.OrgAccountBalance;
     let third_party_trans_id = &validation_data.ThirdPartyTransID;
     let _msisdn = &validation_data.MSISDN;
-    let first_name = &validation_data.FirstName;
-    let middle_name = &validation_data.MiddleName;
-    let last_name = &validation_data.LastName;
+    let first_name = &validation_data.FirstName.as_ref().unwrap_or(&k);
+    let middle_name = &validation_data.MiddleName.as_ref().unwrap_or(&k);
+    let last_name = &validation_data.LastName.as_ref().unwrap_or(&k);
     let bill_type = &C2B_BILL_TYPE;
 
     let response_status = create_incoming_c2b_mpesa_validation_requests(
@@ -738,9 +726,9 @@ pub(crate) async fn confirmation_c2b(
     let org_account_balance = &confirmation_data.OrgAccountBalance;
     let third_party_trans_id = &confirmation_data.ThirdPartyTransID;
     let _msisdn = &confirmation_data.MSISDN;
-    let first_name = &confirmation_data.FirstName;
-    let middle_name = &confirmation_data.MiddleName;
-    let last_name = &confirmation_data.LastName;
+    let first_name = &confirmation_data.FirstName.as_ref().unwrap_or(&k);
+    let middle_name = &confirmation_data.MiddleName.as_ref().unwrap_or(&k);
+    let last_name = &confirmation_data.LastName.as_ref().unwrap_or(&k);
     let bill_type = &C2B_BILL_TYPE;
 
     let response_status = create_incoming_c2b_mpesa_confirmation_requests(
@@ -786,15 +774,25 @@ fn get_api_key(data: &web::Data<Pool>) -> String {
     api_key
 }
 
-fn get_register_url_details(data: &web::Data<Pool>) -> Result<RegisterUrlInputDetails, String> {
-    let api_url = get_settings_details(&data, String::from("c2bregisterurlmpesa"));
+// Setting environmentmpesa is either "sandbox", "production" or the base url of a local
+// stand-in (e.g. http://localhost:8080); MpesaGateway::new rejects any other value
+fn get_environment(data: &web::Data<Pool>) -> Environment {
+    let environment: String = get_settings_details(&data, String::from("environmentmpesa"));
+
+    match environment.trim().to_lowercase().as_str() {
+        "sandbox" => Environment::Sandbox,
+        "production" => Environment::Production,
+        _ => Environment::Custom(environment),
+    }
+}
+
+fn get_register_url_details(data: &web::Data<Pool>) -> Result<RegisterUrlInputDetails, MpesaError> {
     let short_code = get_settings_details(&data, String::from("c2bregisterbusinessshortcodempesa"));
     let response_type = get_settings_details(&data, String::from("c2bregisterresponsetypempesa"));
     let confirmation_url = get_settings_details(&data, String::from("confirmationc2burlmpesa"));
     let validation_url = get_settings_details(&data, String::from("validationc2burlmpesa"));
     /*
     let register_url_details = RegisterUrlInputDetails {
-        //api_url: api_url,
         short_code: short_code,
         response_type: response_type,
         confirmation_url: confirmation_url,
@@ -802,7 +800,7 @@ fn get_register_url_details(data: &web::Data<Pool>) -> Result<RegisterUrlInputDe
     };
     */
     let _result = RegisterUrlInputDetails::new(
-        api_url,
+        None,
         short_code,
         response_type,
         confirmation_url,
@@ -819,8 +817,7 @@ fn get_business_to_customer_details(
     my_command_id: String,
     my_remarks: String,
     my_occassion: String,
-) -> Result<BusinessToCustomerInputDetails, String> {
-    let my_api_url: String = get_settings_details(&data, String::from("b2cpaymentrequesturlmpesa"));
+) -> Result<BusinessToCustomerInputDetails, MpesaError> {
     let my_originator_conversation_id = Local::now().format("%Y%m%d%H%M%S%3f").to_string(); // test only
     let my_initiator_name: String =
         get_settings_details(&data, String::from("b2cinitiatornamempesa"));
@@ -838,7 +835,6 @@ fn get_business_to_customer_details(
     };
     /*
     let business_to_customer_data = BusinessToCustomerInputDetails {
-        //api_url: my_api_url,
         initiator_name: my_initiator_name,
         security_credential: my_security_credential,
         command_id: my_command_id,
@@ -855,7 +851,7 @@ fn get_business_to_customer_details(
     */
 
     let _result = BusinessToCustomerInputDetails::new(
-        my_api_url,
+        None,
         my_originator_conversation_id,
         my_initiator_name,
         my_security_credential,
@@ -0,0 +1,103 @@
+use reqwest::StatusCode;
+
+use crate::{
+    error::error::MpesaError,
+    models::models::{
+        AccountBalanceErrorResponseData, AccountBalanceInputDetails, AccountBalanceResponseData,
+    },
+    util::util::{build_account_balance_data, build_headers},
+};
+
+pub async fn account_balance(
+    client: &reqwest::Client,
+    account_balance_details: &AccountBalanceInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<AccountBalanceResponseData>,
+        Option<AccountBalanceErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let _initiator: String = account_balance_details.get_initiator();
+    let security_credential: String = account_balance_details.get_security_credential();
+    let command_id: String = account_balance_details.get_command_id();
+    let party_a: String = account_balance_details.get_party_a();
+    let identifier_type: String = account_balance_details.get_identifier_type();
+    let _remarks: String = account_balance_details.get_remarks();
+    let queue_time_out_url: String = account_balance_details.get_queue_time_out_url();
+    let result_url: String = account_balance_details.get_result_url();
+
+    let account_balance_data = build_account_balance_data(
+        _initiator,
+        security_credential,
+        command_id,
+        party_a,
+        identifier_type,
+        _remarks,
+        queue_time_out_url,
+        result_url,
+    );
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&account_balance_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response.json::<AccountBalanceResponseData>().await {
+                    Ok(account_balance_response_data) => {
+                        // Handle success case
+                        let account_balance_error_response_data = None;
+                        let my_output = (
+                            Some(account_balance_response_data),
+                            account_balance_error_response_data,
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<AccountBalanceErrorResponseData>(&body) {
+                    Ok(account_balance_error_response_data) => {
+                        // Handle success case
+                        let account_balance_response_data = None;
+                        let my_output = (
+                            account_balance_response_data,
+                            Some(account_balance_error_response_data),
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
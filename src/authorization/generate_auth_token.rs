@@ -1,13 +1,15 @@
 use reqwest::StatusCode;
 
-use crate::{models::models::AuthTokenResponseData, util::util::build_headers_generate_auth_token};
+use crate::{
+    error::error::MpesaError, models::models::AuthTokenResponseData,
+    util::util::build_headers_generate_auth_token,
+};
 
 pub async fn get_auth_token(
+    client: &reqwest::Client,
     api_key: String,
     api_url: String,
-) -> std::result::Result<String, String> {
-    let client = reqwest::Client::new();
-
+) -> std::result::Result<AuthTokenResponseData, MpesaError> {
     let res = client
         .get(api_url)
         .headers(build_headers_generate_auth_token(api_key))
@@ -16,7 +18,7 @@ pub async fn get_auth_token(
 
     match res {
         Err(_err) => {
-            return Err(_err.to_string());
+            return Err(MpesaError::Transport(_err.to_string()));
         }
         Ok(response) => {
             match response.status() {
@@ -24,21 +26,21 @@ pub async fn get_auth_token(
                     match response.json::<AuthTokenResponseData>().await {
                         Ok(auth_token_data) => {
                             // Handle success case
-                            let k = String::from(""); //Default value.
-                            let access_token = auth_token_data.access_token.as_ref().unwrap_or(&k);
-
-                            return Ok(access_token.to_string());
+                            return Ok(auth_token_data);
                         }
                         Err(_err) => {
                             // Handle error case
-                            return Err(_err.to_string());
+                            return Err(MpesaError::JsonDecode(_err.to_string()));
                         }
                     }
                 }
                 s => {
-                    let mut _x = String::from("Request failed processing, status code: ");
-                    _x.push_str(&s.to_string());
-                    return Err(_x.to_string());
+                    let body: String = response.text().await.unwrap_or_default();
+
+                    return Err(MpesaError::Auth(format!(
+                        "Request failed processing, status code: {}, body: {}",
+                        s, body
+                    )));
                 }
             }
         }
@@ -0,0 +1,104 @@
+use reqwest::StatusCode;
+
+use crate::{
+    error::error::MpesaError,
+    models::models::{B2PochiErrorResponseData, B2PochiInputDetails, B2PochiResponseData},
+    util::util::{build_b2_pochi_data, build_headers},
+};
+
+// pay a customer's Pochi la Biashara account
+pub async fn pay_to_pochi(
+    client: &reqwest::Client,
+    b2_pochi_details: &B2PochiInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<B2PochiResponseData>,
+        Option<B2PochiErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let originator_conversation_id: String = b2_pochi_details.get_originator_conversation_id();
+    let initiator_name: String = b2_pochi_details.get_initiator_name();
+    let security_credential: String = b2_pochi_details.get_security_credential();
+    let command_id: String = b2_pochi_details.get_command_id();
+    let amount: u32 = b2_pochi_details.get_amount();
+    let party_a: u32 = b2_pochi_details.get_party_a();
+    let party_b: String = b2_pochi_details.get_party_b();
+    let _remarks: String = b2_pochi_details.get_remarks();
+    let queue_time_out_url: String = b2_pochi_details.get_queue_time_out_url();
+    let result_url: String = b2_pochi_details.get_result_url();
+    let _occasion: String = b2_pochi_details.get_occasion();
+
+    let b2_pochi_data = build_b2_pochi_data(
+        originator_conversation_id,
+        initiator_name,
+        security_credential,
+        command_id,
+        amount,
+        party_a,
+        party_b,
+        _remarks,
+        queue_time_out_url,
+        result_url,
+        _occasion,
+    );
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&b2_pochi_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response.json::<B2PochiResponseData>().await {
+                    Ok(b2_pochi_response_data) => {
+                        // Handle success case
+                        let b2_pochi_error_response_data = None;
+                        let my_output =
+                            (Some(b2_pochi_response_data), b2_pochi_error_response_data);
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<B2PochiErrorResponseData>(&body) {
+                    Ok(b2_pochi_error_response_data) => {
+                        // Handle success case
+                        let b2_pochi_response_data = None;
+                        let my_output =
+                            (b2_pochi_response_data, Some(b2_pochi_error_response_data));
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
@@ -0,0 +1,115 @@
+use reqwest::StatusCode;
+
+use crate::{
+    error::error::MpesaError,
+    models::models::{
+        B2CAccountTopUpErrorResponseData, B2CAccountTopUpInputDetails, B2CAccountTopUpResponseData,
+    },
+    util::util::{build_b2c_account_top_up_data, build_headers},
+};
+
+// move funds from the working account to the B2C utility account
+pub async fn top_up(
+    client: &reqwest::Client,
+    b2c_account_top_up_details: &B2CAccountTopUpInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<B2CAccountTopUpResponseData>,
+        Option<B2CAccountTopUpErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let _initiator: String = b2c_account_top_up_details.get_initiator();
+    let security_credential: String = b2c_account_top_up_details.get_security_credential();
+    let command_id: String = b2c_account_top_up_details.get_command_id();
+    let sender_identifier_type: String = b2c_account_top_up_details.get_sender_identifier_type();
+    let reciever_identifier_type: String =
+        b2c_account_top_up_details.get_reciever_identifier_type();
+    let _amount: u32 = b2c_account_top_up_details.get_amount();
+    let party_a: String = b2c_account_top_up_details.get_party_a();
+    let party_b: String = b2c_account_top_up_details.get_party_b();
+    let account_reference: String = b2c_account_top_up_details.get_account_reference();
+    let _requester: Option<String> = b2c_account_top_up_details.get_requester();
+    let _remarks: String = b2c_account_top_up_details.get_remarks();
+    let queue_time_out_url: String = b2c_account_top_up_details.get_queue_time_out_url();
+    let result_url: String = b2c_account_top_up_details.get_result_url();
+
+    let b2c_account_top_up_data = build_b2c_account_top_up_data(
+        _initiator,
+        security_credential,
+        command_id,
+        sender_identifier_type,
+        reciever_identifier_type,
+        _amount,
+        party_a,
+        party_b,
+        account_reference,
+        _requester,
+        _remarks,
+        queue_time_out_url,
+        result_url,
+    );
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&b2c_account_top_up_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response.json::<B2CAccountTopUpResponseData>().await {
+                    Ok(b2c_account_top_up_response_data) => {
+                        // Handle success case
+                        let b2c_account_top_up_error_response_data = None;
+                        let my_output = (
+                            Some(b2c_account_top_up_response_data),
+                            b2c_account_top_up_error_response_data,
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<B2CAccountTopUpErrorResponseData>(&body) {
+                    Ok(b2c_account_top_up_error_response_data) => {
+                        // Handle success case
+                        let b2c_account_top_up_response_data = None;
+                        let my_output = (
+                            b2c_account_top_up_response_data,
+                            Some(b2c_account_top_up_error_response_data),
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
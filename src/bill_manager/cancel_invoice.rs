@@ -0,0 +1,181 @@
+use reqwest::StatusCode;
+
+use crate::{
+    error::error::MpesaError,
+    models::models::{
+        BillManagerCancelBulkInvoiceInputDetails, BillManagerCancelInvoiceData,
+        BillManagerCancelInvoiceInputDetails, BillManagerCancelInvoiceResponseData,
+        BillManagerErrorResponseData,
+    },
+    util::util::{build_bill_manager_cancel_invoice_data, build_headers},
+};
+
+// cancel a single invoice sent through bill manager
+pub async fn cancel_single_invoice(
+    client: &reqwest::Client,
+    bill_manager_cancel_invoice_details: &BillManagerCancelInvoiceInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<BillManagerCancelInvoiceResponseData>,
+        Option<BillManagerErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let external_reference: String = bill_manager_cancel_invoice_details.get_external_reference();
+
+    let bill_manager_cancel_invoice_data =
+        build_bill_manager_cancel_invoice_data(external_reference);
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&bill_manager_cancel_invoice_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response
+                    .json::<BillManagerCancelInvoiceResponseData>()
+                    .await
+                {
+                    Ok(bill_manager_cancel_invoice_response_data) => {
+                        // Handle success case
+                        let bill_manager_cancel_invoice_error_response_data = None;
+                        let my_output = (
+                            Some(bill_manager_cancel_invoice_response_data),
+                            bill_manager_cancel_invoice_error_response_data,
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<BillManagerErrorResponseData>(&body) {
+                    Ok(bill_manager_cancel_invoice_error_response_data) => {
+                        // Handle success case
+                        let bill_manager_cancel_invoice_response_data = None;
+                        let my_output = (
+                            bill_manager_cancel_invoice_response_data,
+                            Some(bill_manager_cancel_invoice_error_response_data),
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
+
+// cancel several invoices sent through bill manager
+pub async fn cancel_bulk_invoices(
+    client: &reqwest::Client,
+    bill_manager_cancel_bulk_invoice_details: &BillManagerCancelBulkInvoiceInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<BillManagerCancelInvoiceResponseData>,
+        Option<BillManagerErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let bill_manager_cancel_bulk_invoice_data: Vec<BillManagerCancelInvoiceData> =
+        bill_manager_cancel_bulk_invoice_details
+            .get_external_references()
+            .iter()
+            .map(|external_reference| {
+                build_bill_manager_cancel_invoice_data(external_reference.to_string())
+            })
+            .collect();
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&bill_manager_cancel_bulk_invoice_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response
+                    .json::<BillManagerCancelInvoiceResponseData>()
+                    .await
+                {
+                    Ok(bill_manager_cancel_bulk_invoice_response_data) => {
+                        // Handle success case
+                        let bill_manager_cancel_bulk_invoice_error_response_data = None;
+                        let my_output = (
+                            Some(bill_manager_cancel_bulk_invoice_response_data),
+                            bill_manager_cancel_bulk_invoice_error_response_data,
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<BillManagerErrorResponseData>(&body) {
+                    Ok(bill_manager_cancel_bulk_invoice_error_response_data) => {
+                        // Handle success case
+                        let bill_manager_cancel_bulk_invoice_response_data = None;
+                        let my_output = (
+                            bill_manager_cancel_bulk_invoice_response_data,
+                            Some(bill_manager_cancel_bulk_invoice_error_response_data),
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
@@ -0,0 +1,168 @@
+use reqwest::StatusCode;
+
+use crate::{
+    error::error::MpesaError,
+    models::models::{
+        BillManagerBulkInvoiceInputDetails, BillManagerErrorResponseData, BillManagerInvoiceData,
+        BillManagerInvoiceResponseData, BillManagerSingleInvoiceInputDetails,
+    },
+    util::util::{build_bill_manager_invoice_data, build_headers},
+};
+
+pub async fn single_invoicing(
+    client: &reqwest::Client,
+    bill_manager_single_invoice_details: &BillManagerSingleInvoiceInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<BillManagerInvoiceResponseData>,
+        Option<BillManagerErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let bill_manager_single_invoice_data =
+        build_bill_manager_invoice_data(bill_manager_single_invoice_details.get_invoice());
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&bill_manager_single_invoice_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response.json::<BillManagerInvoiceResponseData>().await {
+                    Ok(bill_manager_single_invoice_response_data) => {
+                        // Handle success case
+                        let bill_manager_single_invoice_error_response_data = None;
+                        let my_output = (
+                            Some(bill_manager_single_invoice_response_data),
+                            bill_manager_single_invoice_error_response_data,
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<BillManagerErrorResponseData>(&body) {
+                    Ok(bill_manager_single_invoice_error_response_data) => {
+                        // Handle success case
+                        let bill_manager_single_invoice_response_data = None;
+                        let my_output = (
+                            bill_manager_single_invoice_response_data,
+                            Some(bill_manager_single_invoice_error_response_data),
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
+
+pub async fn bulk_invoicing(
+    client: &reqwest::Client,
+    bill_manager_bulk_invoice_details: &BillManagerBulkInvoiceInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<BillManagerInvoiceResponseData>,
+        Option<BillManagerErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let bill_manager_bulk_invoice_data: Vec<BillManagerInvoiceData> =
+        bill_manager_bulk_invoice_details
+            .get_invoices()
+            .iter()
+            .map(build_bill_manager_invoice_data)
+            .collect();
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&bill_manager_bulk_invoice_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response.json::<BillManagerInvoiceResponseData>().await {
+                    Ok(bill_manager_bulk_invoice_response_data) => {
+                        // Handle success case
+                        let bill_manager_bulk_invoice_error_response_data = None;
+                        let my_output = (
+                            Some(bill_manager_bulk_invoice_response_data),
+                            bill_manager_bulk_invoice_error_response_data,
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<BillManagerErrorResponseData>(&body) {
+                    Ok(bill_manager_bulk_invoice_error_response_data) => {
+                        // Handle success case
+                        let bill_manager_bulk_invoice_response_data = None;
+                        let my_output = (
+                            bill_manager_bulk_invoice_response_data,
+                            Some(bill_manager_bulk_invoice_error_response_data),
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
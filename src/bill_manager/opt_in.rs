@@ -0,0 +1,191 @@
+use reqwest::StatusCode;
+
+use crate::{
+    error::error::MpesaError,
+    models::models::{
+        BillManagerErrorResponseData, BillManagerOptInInputDetails, BillManagerOptInResponseData,
+    },
+    util::util::{build_bill_manager_opt_in_data, build_headers},
+};
+
+// onboard a paybill to bill manager
+pub async fn opt_in(
+    client: &reqwest::Client,
+    bill_manager_opt_in_details: &BillManagerOptInInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<BillManagerOptInResponseData>,
+        Option<BillManagerErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let short_code: String = bill_manager_opt_in_details.get_short_code();
+    let _email: String = bill_manager_opt_in_details.get_email();
+    let official_contact: String = bill_manager_opt_in_details.get_official_contact();
+    let send_reminders: String = bill_manager_opt_in_details.get_send_reminders();
+    let _logo: String = bill_manager_opt_in_details.get_logo();
+    let callback_url: String = bill_manager_opt_in_details.get_callback_url();
+
+    let bill_manager_opt_in_data = build_bill_manager_opt_in_data(
+        short_code,
+        _email,
+        official_contact,
+        send_reminders,
+        _logo,
+        callback_url,
+    );
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&bill_manager_opt_in_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response.json::<BillManagerOptInResponseData>().await {
+                    Ok(bill_manager_opt_in_response_data) => {
+                        // Handle success case
+                        let bill_manager_opt_in_error_response_data = None;
+                        let my_output = (
+                            Some(bill_manager_opt_in_response_data),
+                            bill_manager_opt_in_error_response_data,
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<BillManagerErrorResponseData>(&body) {
+                    Ok(bill_manager_opt_in_error_response_data) => {
+                        // Handle success case
+                        let bill_manager_opt_in_response_data = None;
+                        let my_output = (
+                            bill_manager_opt_in_response_data,
+                            Some(bill_manager_opt_in_error_response_data),
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
+
+// update the details given during opt-in
+pub async fn update_opt_in(
+    client: &reqwest::Client,
+    bill_manager_opt_in_details: &BillManagerOptInInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<BillManagerOptInResponseData>,
+        Option<BillManagerErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let short_code: String = bill_manager_opt_in_details.get_short_code();
+    let _email: String = bill_manager_opt_in_details.get_email();
+    let official_contact: String = bill_manager_opt_in_details.get_official_contact();
+    let send_reminders: String = bill_manager_opt_in_details.get_send_reminders();
+    let _logo: String = bill_manager_opt_in_details.get_logo();
+    let callback_url: String = bill_manager_opt_in_details.get_callback_url();
+
+    let bill_manager_opt_in_data = build_bill_manager_opt_in_data(
+        short_code,
+        _email,
+        official_contact,
+        send_reminders,
+        _logo,
+        callback_url,
+    );
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&bill_manager_opt_in_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response.json::<BillManagerOptInResponseData>().await {
+                    Ok(bill_manager_opt_in_response_data) => {
+                        // Handle success case
+                        let bill_manager_opt_in_error_response_data = None;
+                        let my_output = (
+                            Some(bill_manager_opt_in_response_data),
+                            bill_manager_opt_in_error_response_data,
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<BillManagerErrorResponseData>(&body) {
+                    Ok(bill_manager_opt_in_error_response_data) => {
+                        // Handle success case
+                        let bill_manager_opt_in_response_data = None;
+                        let my_output = (
+                            bill_manager_opt_in_response_data,
+                            Some(bill_manager_opt_in_error_response_data),
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
@@ -0,0 +1,108 @@
+use reqwest::StatusCode;
+
+use crate::{
+    error::error::MpesaError,
+    models::models::{
+        BillManagerErrorResponseData, BillManagerReconciliationInputDetails,
+        BillManagerReconciliationResponseData,
+    },
+    util::util::{build_bill_manager_reconciliation_data, build_headers},
+};
+
+// acknowledge a payment notification received from bill manager
+pub async fn reconciliation(
+    client: &reqwest::Client,
+    bill_manager_reconciliation_details: &BillManagerReconciliationInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<BillManagerReconciliationResponseData>,
+        Option<BillManagerErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let payment_date: String = bill_manager_reconciliation_details.get_payment_date();
+    let paid_amount: u32 = bill_manager_reconciliation_details.get_paid_amount();
+    let account_reference: String = bill_manager_reconciliation_details.get_account_reference();
+    let transaction_id: String = bill_manager_reconciliation_details.get_transaction_id();
+    let phone_number: String = bill_manager_reconciliation_details.get_phone_number();
+    let full_name: String = bill_manager_reconciliation_details.get_full_name();
+    let invoice_name: String = bill_manager_reconciliation_details.get_invoice_name();
+    let external_reference: String = bill_manager_reconciliation_details.get_external_reference();
+
+    let bill_manager_reconciliation_data = build_bill_manager_reconciliation_data(
+        payment_date,
+        paid_amount,
+        account_reference,
+        transaction_id,
+        phone_number,
+        full_name,
+        invoice_name,
+        external_reference,
+    );
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&bill_manager_reconciliation_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response
+                    .json::<BillManagerReconciliationResponseData>()
+                    .await
+                {
+                    Ok(bill_manager_reconciliation_response_data) => {
+                        // Handle success case
+                        let bill_manager_reconciliation_error_response_data = None;
+                        let my_output = (
+                            Some(bill_manager_reconciliation_response_data),
+                            bill_manager_reconciliation_error_response_data,
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<BillManagerErrorResponseData>(&body) {
+                    Ok(bill_manager_reconciliation_error_response_data) => {
+                        // Handle success case
+                        let bill_manager_reconciliation_response_data = None;
+                        let my_output = (
+                            bill_manager_reconciliation_response_data,
+                            Some(bill_manager_reconciliation_error_response_data),
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
@@ -1,6 +1,7 @@
 use reqwest::StatusCode;
 
 use crate::{
+    error::error::MpesaError,
     models::models::{
         BusinessBuyGoodsErrorResponseData, BusinessBuyGoodsInputDetails,
         BusinessBuyGoodsResponseData,
@@ -9,16 +10,17 @@ use crate::{
 };
 
 pub async fn buy_goods(
-    business_buy_goods_details: BusinessBuyGoodsInputDetails,
+    client: &reqwest::Client,
+    business_buy_goods_details: &BusinessBuyGoodsInputDetails,
+    api_url: String,
     access_token: String,
 ) -> std::result::Result<
     (
         Option<BusinessBuyGoodsResponseData>,
         Option<BusinessBuyGoodsErrorResponseData>,
     ),
-    String,
+    MpesaError,
 > {
-    let api_url: String = business_buy_goods_details.get_api_url();
     let _initiator: String = business_buy_goods_details.get_initiator();
     let security_credential: String = business_buy_goods_details.get_security_credential();
     let command_id: String = business_buy_goods_details.get_command_id();
@@ -50,8 +52,6 @@ pub async fn buy_goods(
         result_url,
     );
 
-    let client = reqwest::Client::new();
-
     let res = client
         .post(api_url)
         .headers(build_headers(access_token))
@@ -61,7 +61,7 @@ pub async fn buy_goods(
 
     match res {
         Err(_err) => {
-            return Err(_err.to_string());
+            return Err(MpesaError::Transport(_err.to_string()));
         }
         Ok(response) => match response.status() {
             StatusCode::OK => {
@@ -77,12 +77,19 @@ pub async fn buy_goods(
                     }
                     Err(_err) => {
                         // Handle error case
-                        return Err(_err.to_string());
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
                     }
                 }
             }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
             s => {
-                match response.json::<BusinessBuyGoodsErrorResponseData>().await {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<BusinessBuyGoodsErrorResponseData>(&body) {
                     Ok(business_buy_goods_error_response_data) => {
                         // Handle success case
                         let business_buy_goods_response_data = None;
@@ -94,7 +101,10 @@ pub async fn buy_goods(
                     }
                     Err(_err) => {
                         // Handle error case
-                        return Err(_err.to_string());
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
                     }
                 }
             }
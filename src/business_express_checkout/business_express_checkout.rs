@@ -0,0 +1,103 @@
+use reqwest::StatusCode;
+
+use crate::{
+    error::error::MpesaError,
+    models::models::{
+        BusinessExpressCheckoutErrorResponseData, BusinessExpressCheckoutInputDetails,
+        BusinessExpressCheckoutResponseData,
+    },
+    util::util::{build_business_express_checkout_data, build_headers},
+};
+
+// ussd push to merchant till
+pub async fn express_checkout(
+    client: &reqwest::Client,
+    business_express_checkout_details: &BusinessExpressCheckoutInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<BusinessExpressCheckoutResponseData>,
+        Option<BusinessExpressCheckoutErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let primary_short_code: String = business_express_checkout_details.get_primary_short_code();
+    let receiver_short_code: String = business_express_checkout_details.get_receiver_short_code();
+    let _amount: u32 = business_express_checkout_details.get_amount();
+    let payment_ref: String = business_express_checkout_details.get_payment_ref();
+    let callback_url: String = business_express_checkout_details.get_callback_url();
+    let partner_name: String = business_express_checkout_details.get_partner_name();
+    let request_ref_id: String = business_express_checkout_details.get_request_ref_id();
+
+    let business_express_checkout_data = build_business_express_checkout_data(
+        primary_short_code,
+        receiver_short_code,
+        _amount,
+        payment_ref,
+        callback_url,
+        partner_name,
+        request_ref_id,
+    );
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&business_express_checkout_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response.json::<BusinessExpressCheckoutResponseData>().await {
+                    Ok(business_express_checkout_response_data) => {
+                        // Handle success case
+                        let business_express_checkout_error_response_data = None;
+                        let my_output = (
+                            Some(business_express_checkout_response_data),
+                            business_express_checkout_error_response_data,
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<BusinessExpressCheckoutErrorResponseData>(&body) {
+                    Ok(business_express_checkout_error_response_data) => {
+                        // Handle success case
+                        let business_express_checkout_response_data = None;
+                        let my_output = (
+                            business_express_checkout_response_data,
+                            Some(business_express_checkout_error_response_data),
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
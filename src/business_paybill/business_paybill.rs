@@ -1,6 +1,7 @@
 use reqwest::StatusCode;
 
 use crate::{
+    error::error::MpesaError,
     models::models::{
         BusinessPayBillErrorResponseData, BusinessPayBillInputDetails, BusinessPayBillResponseData,
     },
@@ -8,16 +9,17 @@ use crate::{
 };
 
 pub async fn pay_bill(
-    business_paybill_details: BusinessPayBillInputDetails,
+    client: &reqwest::Client,
+    business_paybill_details: &BusinessPayBillInputDetails,
+    api_url: String,
     access_token: String,
 ) -> std::result::Result<
     (
         Option<BusinessPayBillResponseData>,
         Option<BusinessPayBillErrorResponseData>,
     ),
-    String,
+    MpesaError,
 > {
-    let api_url: String = business_paybill_details.get_api_url();
     let _initiator: String = business_paybill_details.get_initiator();
     let security_credential: String = business_paybill_details.get_security_credential();
     let command_id: String = business_paybill_details.get_command_id();
@@ -48,8 +50,6 @@ pub async fn pay_bill(
         result_url,
     );
 
-    let client = reqwest::Client::new();
-
     let res = client
         .post(api_url)
         .headers(build_headers(access_token))
@@ -59,7 +59,7 @@ pub async fn pay_bill(
 
     match res {
         Err(_err) => {
-            return Err(_err.to_string());
+            return Err(MpesaError::Transport(_err.to_string()));
         }
         Ok(response) => match response.status() {
             StatusCode::OK => {
@@ -76,12 +76,19 @@ pub async fn pay_bill(
                     }
                     Err(_err) => {
                         // Handle error case
-                        return Err(_err.to_string());
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
                     }
                 }
             }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
             s => {
-                match response.json::<BusinessPayBillErrorResponseData>().await {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<BusinessPayBillErrorResponseData>(&body) {
                     Ok(business_paybill_error_response_data) => {
                         // Handle success case
                         let business_paybill_response_data = None;
@@ -94,7 +101,10 @@ pub async fn pay_bill(
                     }
                     Err(_err) => {
                         // Handle error case
-                        return Err(_err.to_string());
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
                     }
                 }
             }
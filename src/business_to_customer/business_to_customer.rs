@@ -1,6 +1,7 @@
 use reqwest::StatusCode;
 
 use crate::{
+    error::error::MpesaError,
     models::models::{
         BusinessToCustomerErrorResponseData, BusinessToCustomerInputDetails,
         BusinessToCustomerResponseData,
@@ -9,16 +10,17 @@ use crate::{
 };
 
 pub async fn b2c(
-    business_to_customer_details: BusinessToCustomerInputDetails,
+    client: &reqwest::Client,
+    business_to_customer_details: &BusinessToCustomerInputDetails,
+    api_url: String,
     access_token: String,
 ) -> std::result::Result<
     (
         Option<BusinessToCustomerResponseData>,
         Option<BusinessToCustomerErrorResponseData>,
     ),
-    String,
+    MpesaError,
 > {
-    let api_url: String = business_to_customer_details.get_api_url();
     let originator_conversation_id = business_to_customer_details.get_originator_conversation_id();
     let initiator_name: String = business_to_customer_details.get_initiator_name();
     let security_credential: String = business_to_customer_details.get_security_credential();
@@ -45,8 +47,6 @@ pub async fn b2c(
         _occassion,
     );
 
-    let client = reqwest::Client::new();
-
     let res = client
         .post(api_url)
         .headers(build_headers(access_token))
@@ -56,7 +56,7 @@ pub async fn b2c(
 
     match res {
         Err(_err) => {
-            return Err(_err.to_string());
+            return Err(MpesaError::Transport(_err.to_string()));
         }
         Ok(response) => match response.status() {
             StatusCode::OK => {
@@ -74,12 +74,19 @@ pub async fn b2c(
                     }
                     Err(_err) => {
                         // Handle error case
-                        return Err(_err.to_string());
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
                     }
                 }
             }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
             s => {
-                match response.json::<BusinessToCustomerErrorResponseData>().await {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<BusinessToCustomerErrorResponseData>(&body) {
                     Ok(business_to_customer_error_response_data) => {
                         // Handle success case
                         let business_to_customer_response_data = None;
@@ -92,7 +99,10 @@ pub async fn b2c(
                     }
                     Err(_err) => {
                         // Handle error case
-                        return Err(_err.to_string());
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
                     }
                 }
             }
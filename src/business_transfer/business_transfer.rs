@@ -0,0 +1,115 @@
+use reqwest::StatusCode;
+
+use crate::{
+    error::error::MpesaError,
+    models::models::{
+        BusinessTransferErrorResponseData, BusinessTransferInputDetails,
+        BusinessTransferResponseData,
+    },
+    util::util::{build_business_transfer_data, build_headers},
+};
+
+// move funds between business accounts (b2b transfer command ids)
+pub async fn transfer(
+    client: &reqwest::Client,
+    business_transfer_details: &BusinessTransferInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<BusinessTransferResponseData>,
+        Option<BusinessTransferErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let _initiator: String = business_transfer_details.get_initiator();
+    let security_credential: String = business_transfer_details.get_security_credential();
+    let command_id: String = business_transfer_details.get_command_id();
+    let sender_identifier_type: String = business_transfer_details.get_sender_identifier_type();
+    let reciever_identifier_type: String = business_transfer_details.get_reciever_identifier_type();
+    let _amount: u32 = business_transfer_details.get_amount();
+    let party_a: String = business_transfer_details.get_party_a();
+    let party_b: String = business_transfer_details.get_party_b();
+    let account_reference: String = business_transfer_details.get_account_reference();
+    let _requester: Option<String> = business_transfer_details.get_requester();
+    let _remarks: String = business_transfer_details.get_remarks();
+    let queue_time_out_url: String = business_transfer_details.get_queue_time_out_url();
+    let result_url: String = business_transfer_details.get_result_url();
+
+    let business_transfer_data = build_business_transfer_data(
+        _initiator,
+        security_credential,
+        command_id,
+        sender_identifier_type,
+        reciever_identifier_type,
+        _amount,
+        party_a,
+        party_b,
+        account_reference,
+        _requester,
+        _remarks,
+        queue_time_out_url,
+        result_url,
+    );
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&business_transfer_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response.json::<BusinessTransferResponseData>().await {
+                    Ok(business_transfer_response_data) => {
+                        // Handle success case
+                        let business_transfer_error_response_data = None;
+                        let my_output = (
+                            Some(business_transfer_response_data),
+                            business_transfer_error_response_data,
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<BusinessTransferErrorResponseData>(&body) {
+                    Ok(business_transfer_error_response_data) => {
+                        // Handle success case
+                        let business_transfer_response_data = None;
+                        let my_output = (
+                            business_transfer_response_data,
+                            Some(business_transfer_error_response_data),
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
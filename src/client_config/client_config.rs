@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use reqwest::{Certificate, Client, Proxy};
+
+use crate::error::error::MpesaError;
+
+// Settings for the http client that is shared by all the operations of MpesaGateway.
+// Unset values fall back to the reqwest defaults.
+#[derive(Debug, Clone, Default)]
+pub struct ClientConfig {
+    connect_timeout: Option<Duration>,
+    request_timeout: Option<Duration>,
+    proxy: Option<Proxy>,
+    user_agent: Option<String>,
+    root_certificates: Vec<Certificate>,
+    built_in_root_certificates: Option<bool>,
+    pool_max_idle_per_host: Option<usize>,
+}
+
+impl ClientConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    // Covers the whole request, from connecting until the response body has been read
+    pub fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = Some(request_timeout);
+        self
+    }
+
+    // proxy_url e.g. http://10.0.0.1:3128, used for both http and https requests
+    pub fn with_proxy(mut self, proxy_url: String) -> Result<Self, MpesaError> {
+        if proxy_url.is_empty() || proxy_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("proxy url", "is empty"));
+        }
+
+        let proxy = match Proxy::all(proxy_url.trim()) {
+            Ok(x) => x,
+            Err(_) => return Err(MpesaError::validation("proxy url", "has invalid value")),
+        };
+
+        self.proxy = Some(proxy);
+        Ok(self)
+    }
+
+    pub fn with_user_agent(mut self, user_agent: String) -> Result<Self, MpesaError> {
+        if user_agent.is_empty() || user_agent.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("user agent", "is empty"));
+        }
+
+        self.user_agent = Some(user_agent);
+        Ok(self)
+    }
+
+    // Adds a trusted root certificate (PEM or DER encoded), e.g. the CA of a corporate proxy
+    pub fn with_root_certificate(mut self, root_certificate: &[u8]) -> Result<Self, MpesaError> {
+        if root_certificate.is_empty() {
+            return Err(MpesaError::validation("root certificate", "is empty"));
+        }
+
+        let is_pem = String::from_utf8_lossy(root_certificate).contains("-----BEGIN");
+
+        let _result = if is_pem {
+            Certificate::from_pem(root_certificate)
+        } else {
+            Certificate::from_der(root_certificate)
+        };
+
+        match _result {
+            Ok(x) => self.root_certificates.push(x),
+            Err(_) => {
+                return Err(MpesaError::validation(
+                    "root certificate",
+                    "has invalid value",
+                ))
+            }
+        }
+
+        Ok(self)
+    }
+
+    // Set to false to only trust the root certificates added through with_root_certificate
+    pub fn with_built_in_root_certificates(mut self, built_in_root_certificates: bool) -> Self {
+        self.built_in_root_certificates = Some(built_in_root_certificates);
+        self
+    }
+
+    pub fn with_pool_max_idle_per_host(mut self, pool_max_idle_per_host: usize) -> Self {
+        self.pool_max_idle_per_host = Some(pool_max_idle_per_host);
+        self
+    }
+
+    pub fn build_client(&self) -> Result<Client, MpesaError> {
+        let mut client_builder = Client::builder();
+
+        if let Some(x) = self.connect_timeout {
+            client_builder = client_builder.connect_timeout(x);
+        }
+
+        if let Some(x) = self.request_timeout {
+            client_builder = client_builder.timeout(x);
+        }
+
+        if let Some(x) = &self.proxy {
+            client_builder = client_builder.proxy(x.clone());
+        }
+
+        if let Some(x) = &self.user_agent {
+            client_builder = client_builder.user_agent(x.to_string());
+        }
+
+        for root_certificate in self.root_certificates.iter() {
+            client_builder = client_builder.add_root_certificate(root_certificate.clone());
+        }
+
+        if let Some(x) = self.built_in_root_certificates {
+            client_builder = client_builder.tls_built_in_root_certs(x);
+        }
+
+        if let Some(x) = self.pool_max_idle_per_host {
+            client_builder = client_builder.pool_max_idle_per_host(x);
+        }
+
+        match client_builder.build() {
+            Ok(x) => Ok(x),
+            Err(_err) => Err(MpesaError::from(_err)),
+        }
+    }
+}
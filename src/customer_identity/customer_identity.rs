@@ -0,0 +1,91 @@
+use reqwest::StatusCode;
+
+use crate::{
+    error::error::MpesaError,
+    models::models::{
+        CustomerIdentityErrorResponseData, CustomerIdentityInputDetails,
+        CustomerIdentityResponseData,
+    },
+    util::util::{build_customer_identity_data, build_headers},
+};
+
+// check that the id type and id number belong to the msisdn
+pub async fn check_identity(
+    client: &reqwest::Client,
+    customer_identity_details: &CustomerIdentityInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<CustomerIdentityResponseData>,
+        Option<CustomerIdentityErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let _msisdn: u64 = customer_identity_details.get_msisdn();
+    let id_type: String = customer_identity_details.get_id_type().get_code();
+    let id_number: String = customer_identity_details.get_id_number();
+
+    let customer_identity_data = build_customer_identity_data(_msisdn, id_type, id_number);
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&customer_identity_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response.json::<CustomerIdentityResponseData>().await {
+                    Ok(customer_identity_response_data) => {
+                        // Handle success case
+                        let customer_identity_error_response_data = None;
+                        let my_output = (
+                            Some(customer_identity_response_data),
+                            customer_identity_error_response_data,
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<CustomerIdentityErrorResponseData>(&body) {
+                    Ok(customer_identity_error_response_data) => {
+                        // Handle success case
+                        let customer_identity_response_data = None;
+                        let my_output = (
+                            customer_identity_response_data,
+                            Some(customer_identity_error_response_data),
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
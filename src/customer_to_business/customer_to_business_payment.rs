@@ -1,6 +1,7 @@
 use reqwest::StatusCode;
 
 use crate::{
+    error::error::MpesaError,
     models::models::{
         CustomerToBusinessPaymentErrorResponseData, CustomerToBusinessPaymentInputDetails,
         CustomerToBusinessPaymentResponseData,
@@ -10,16 +11,17 @@ use crate::{
 
 // network initiated push
 pub async fn c2b_payment(
-    customer_to_business_payment_details: CustomerToBusinessPaymentInputDetails,
+    client: &reqwest::Client,
+    customer_to_business_payment_details: &CustomerToBusinessPaymentInputDetails,
+    api_url: String,
     access_token: String,
 ) -> std::result::Result<
     (
         Option<CustomerToBusinessPaymentResponseData>,
         Option<CustomerToBusinessPaymentErrorResponseData>,
     ),
-    String,
+    MpesaError,
 > {
-    let api_url: String = customer_to_business_payment_details.get_api_url();
     let business_short_code: String =
         customer_to_business_payment_details.get_business_short_code();
     let _password: String = customer_to_business_payment_details.get_password();
@@ -47,8 +49,6 @@ pub async fn c2b_payment(
         transaction_desc,
     );
 
-    let client = reqwest::Client::new();
-
     let res = client
         .post(api_url)
         .headers(build_headers(access_token))
@@ -58,7 +58,7 @@ pub async fn c2b_payment(
 
     match res {
         Err(_err) => {
-            return Err(_err.to_string());
+            return Err(MpesaError::Transport(_err.to_string()));
         }
         Ok(response) => match response.status() {
             StatusCode::OK => {
@@ -78,15 +78,19 @@ pub async fn c2b_payment(
                     }
                     Err(_err) => {
                         // Handle error case
-                        return Err(_err.to_string());
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
                     }
                 }
             }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
             s => {
-                match response
-                    .json::<CustomerToBusinessPaymentErrorResponseData>()
-                    .await
-                {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<CustomerToBusinessPaymentErrorResponseData>(&body) {
                     Ok(customer_to_business_error_response_data) => {
                         // Handle success case
                         let customer_to_business_response_data = None;
@@ -99,7 +103,10 @@ pub async fn c2b_payment(
                     }
                     Err(_err) => {
                         // Handle error case
-                        return Err(_err.to_string());
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
                     }
                 }
             }
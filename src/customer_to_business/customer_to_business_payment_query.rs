@@ -0,0 +1,103 @@
+use reqwest::StatusCode;
+
+use crate::{
+    error::error::MpesaError,
+    models::models::{
+        CustomerToBusinessPaymentQueryErrorResponseData,
+        CustomerToBusinessPaymentQueryInputDetails, CustomerToBusinessPaymentQueryResponseData,
+    },
+    util::util::{build_customer_to_business_payment_query_data, build_headers},
+};
+
+// query status of network initiated push
+pub async fn c2b_payment_query(
+    client: &reqwest::Client,
+    customer_to_business_payment_query_details: &CustomerToBusinessPaymentQueryInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<CustomerToBusinessPaymentQueryResponseData>,
+        Option<CustomerToBusinessPaymentQueryErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let business_short_code: String =
+        customer_to_business_payment_query_details.get_business_short_code();
+    let _password: String = customer_to_business_payment_query_details.get_password();
+    let time_stamp: String = customer_to_business_payment_query_details.get_time_stamp();
+    let checkout_request_id: String =
+        customer_to_business_payment_query_details.get_checkout_request_id();
+
+    let customer_to_business_payment_query_data = build_customer_to_business_payment_query_data(
+        business_short_code,
+        _password,
+        time_stamp,
+        checkout_request_id,
+    );
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&customer_to_business_payment_query_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response
+                    .json::<CustomerToBusinessPaymentQueryResponseData>()
+                    .await
+                {
+                    Ok(customer_to_business_payment_query_response_data) => {
+                        // Handle success case
+                        let customer_to_business_payment_query_error_response_data = None;
+                        let my_output = (
+                            Some(customer_to_business_payment_query_response_data),
+                            customer_to_business_payment_query_error_response_data,
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<CustomerToBusinessPaymentQueryErrorResponseData>(&body)
+                {
+                    Ok(customer_to_business_payment_query_error_response_data) => {
+                        // Handle success case
+                        let customer_to_business_payment_query_response_data = None;
+                        let my_output = (
+                            customer_to_business_payment_query_response_data,
+                            Some(customer_to_business_payment_query_error_response_data),
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
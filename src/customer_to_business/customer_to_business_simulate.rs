@@ -0,0 +1,102 @@
+use reqwest::StatusCode;
+
+use crate::{
+    error::error::MpesaError,
+    models::models::{
+        CustomerToBusinessSimulateErrorResponseData, CustomerToBusinessSimulateInputDetails,
+        CustomerToBusinessSimulateResponseData,
+    },
+    util::util::{build_customer_to_business_simulate_data, build_headers},
+};
+
+// simulate customer payment to a paybill/till (sandbox only)
+pub async fn c2b_simulate(
+    client: &reqwest::Client,
+    customer_to_business_simulate_details: &CustomerToBusinessSimulateInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<CustomerToBusinessSimulateResponseData>,
+        Option<CustomerToBusinessSimulateErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let short_code: String = customer_to_business_simulate_details.get_short_code();
+    let command_id: String = customer_to_business_simulate_details.get_command_id();
+    let _amount: u32 = customer_to_business_simulate_details.get_amount();
+    let _msisdn: u64 = customer_to_business_simulate_details.get_msisdn();
+    let bill_ref_number: String = customer_to_business_simulate_details.get_bill_ref_number();
+
+    let customer_to_business_simulate_data = build_customer_to_business_simulate_data(
+        short_code,
+        command_id,
+        _amount,
+        _msisdn,
+        bill_ref_number,
+    );
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&customer_to_business_simulate_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response
+                    .json::<CustomerToBusinessSimulateResponseData>()
+                    .await
+                {
+                    Ok(customer_to_business_simulate_response_data) => {
+                        // Handle success case
+                        let customer_to_business_simulate_error_response_data = None;
+                        let my_output = (
+                            Some(customer_to_business_simulate_response_data),
+                            customer_to_business_simulate_error_response_data,
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<CustomerToBusinessSimulateErrorResponseData>(&body) {
+                    Ok(customer_to_business_simulate_error_response_data) => {
+                        // Handle success case
+                        let customer_to_business_simulate_response_data = None;
+                        let my_output = (
+                            customer_to_business_simulate_response_data,
+                            Some(customer_to_business_simulate_error_response_data),
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
@@ -1,15 +1,19 @@
 use reqwest::StatusCode;
 
 use crate::{
-    models::models::{RegisterUrlInputDetails, RegisterUrlResponseData},
+    error::error::MpesaError,
+    models::models::{
+        RegisterUrlErrorResponseData, RegisterUrlInputDetails, RegisterUrlResponseData,
+    },
     util::util::{build_headers, build_register_url_data},
 };
 
 pub async fn register_url(
-    register_url_details: RegisterUrlInputDetails,
+    client: &reqwest::Client,
+    register_url_details: &RegisterUrlInputDetails,
+    api_url: String,
     access_token: String,
-) -> std::result::Result<RegisterUrlResponseData, String> {
-    let api_url: String = register_url_details.get_api_url();
+) -> std::result::Result<RegisterUrlResponseData, MpesaError> {
     let short_code: String = register_url_details.get_short_code();
     let response_type: String = register_url_details.get_response_type();
     let confirmation_url: String = register_url_details.get_confirmation_url();
@@ -18,8 +22,6 @@ pub async fn register_url(
     let register_url_data =
         build_register_url_data(short_code, response_type, confirmation_url, validation_url);
 
-    let client = reqwest::Client::new();
-
     let res = client
         .post(api_url)
         .headers(build_headers(access_token))
@@ -29,7 +31,7 @@ pub async fn register_url(
 
     match res {
         Err(_err) => {
-            return Err(_err.to_string());
+            return Err(MpesaError::Transport(_err.to_string()));
         }
         Ok(response) => match response.status() {
             StatusCode::OK => {
@@ -40,14 +42,31 @@ pub async fn register_url(
                     }
                     Err(_err) => {
                         // Handle error case
-                        return Err(_err.to_string());
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
                     }
                 }
             }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
             s => {
-                let mut _x = String::from("Request failed processing, status code: ");
-                _x.push_str(&s.to_string());
-                return Err(_x.to_string());
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<RegisterUrlErrorResponseData>(&body) {
+                    Ok(register_url_error_response_data) => {
+                        // Handle error response e.g. an invalid access token
+                        return Err(MpesaError::from(register_url_error_response_data));
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
             }
         },
     };
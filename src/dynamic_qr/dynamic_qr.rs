@@ -0,0 +1,91 @@
+use reqwest::StatusCode;
+
+use crate::{
+    error::error::MpesaError,
+    models::models::{DynamicQrErrorResponseData, DynamicQrInputDetails, DynamicQrResponseData},
+    util::util::{build_dynamic_qr_data, build_headers},
+};
+
+pub async fn generate_qr(
+    client: &reqwest::Client,
+    dynamic_qr_details: &DynamicQrInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<DynamicQrResponseData>,
+        Option<DynamicQrErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let merchant_name: String = dynamic_qr_details.get_merchant_name();
+    let ref_no: String = dynamic_qr_details.get_ref_no();
+    let _amount: u32 = dynamic_qr_details.get_amount();
+    let trx_code: String = dynamic_qr_details.get_trx_code();
+    let _cpi: String = dynamic_qr_details.get_cpi();
+    let _size: String = dynamic_qr_details.get_size();
+
+    let dynamic_qr_data =
+        build_dynamic_qr_data(merchant_name, ref_no, _amount, trx_code, _cpi, _size);
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&dynamic_qr_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response.json::<DynamicQrResponseData>().await {
+                    Ok(dynamic_qr_response_data) => {
+                        // Handle success case
+                        let dynamic_qr_error_response_data = None;
+                        let my_output = (
+                            Some(dynamic_qr_response_data),
+                            dynamic_qr_error_response_data,
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<DynamicQrErrorResponseData>(&body) {
+                    Ok(dynamic_qr_error_response_data) => {
+                        // Handle success case
+                        let dynamic_qr_response_data = None;
+                        let my_output = (
+                            dynamic_qr_response_data,
+                            Some(dynamic_qr_error_response_data),
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
@@ -0,0 +1,146 @@
+use crate::error::error::MpesaError;
+
+const SANDBOX_BASE_URL: &str = "https://sandbox.safaricom.co.ke";
+const PRODUCTION_BASE_URL: &str = "https://api.safaricom.co.ke";
+const PRODUCTION_HOST: &str = "api.safaricom.co.ke";
+
+// Daraja environment the sdk sends its requests to. Custom takes a base url
+// (e.g. http://localhost:8080) and is meant for pointing the sdk at a local stand-in.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Environment {
+    Sandbox,
+    Production,
+    Custom(String),
+}
+
+impl Environment {
+    pub fn get_base_url(&self) -> String {
+        match self {
+            Environment::Sandbox => SANDBOX_BASE_URL.to_string(),
+            Environment::Production => PRODUCTION_BASE_URL.to_string(),
+            Environment::Custom(base_url) => base_url.trim().trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn get_endpoint_url(&self, endpoint: Endpoint) -> String {
+        let mut endpoint_url = self.get_base_url();
+        endpoint_url.push_str(endpoint.get_path());
+
+        endpoint_url
+    }
+
+    // A custom base url that points at the production api counts as production
+    pub fn is_production(&self) -> bool {
+        match self {
+            Environment::Sandbox => false,
+            Environment::Production => true,
+            Environment::Custom(base_url) => is_production_url(base_url),
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), MpesaError> {
+        if let Environment::Custom(base_url) = self {
+            if base_url.is_empty() || base_url.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("custom base url", "is empty"));
+            }
+
+            let base_url = base_url.trim().to_lowercase();
+
+            if !(base_url.starts_with("http://") || base_url.starts_with("https://")) {
+                return Err(MpesaError::validation(
+                    "custom base url",
+                    "has invalid value",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Compares the host of the url, regardless of scheme, letter case, port or path
+// e.g. HTTP://API.Safaricom.co.ke:443/mpesa/ is a production url
+pub(crate) fn is_production_url(url: &str) -> bool {
+    match reqwest::Url::parse(url.trim()) {
+        Ok(x) => match x.host_str() {
+            Some(host) => host
+                .trim_end_matches('.')
+                .eq_ignore_ascii_case(PRODUCTION_HOST),
+            None => false,
+        },
+        // e.g. a url without a scheme
+        Err(_) => url.to_lowercase().contains(PRODUCTION_HOST),
+    }
+}
+
+// Endpoint catalog for every api supported by the sdk
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Endpoint {
+    AuthToken,
+    RegisterUrl,
+    RegisterUrlV2,
+    BusinessToCustomer,
+    CustomerToBusinessPayment,
+    CustomerToBusinessPaymentQuery,
+    CustomerToBusinessSimulate,
+    BusinessPayBill,
+    BusinessBuyGoods,
+    TransactionStatus,
+    AccountBalance,
+    Reversal,
+    BusinessExpressCheckout,
+    DynamicQr,
+    RemitTax,
+    BillManagerOptIn,
+    BillManagerUpdateOptIn,
+    BillManagerSingleInvoicing,
+    BillManagerBulkInvoicing,
+    BillManagerReconciliation,
+    BillManagerCancelInvoice,
+    BillManagerCancelBulkInvoices,
+    PullTransactionsRegister,
+    PullTransactionsQuery,
+    B2CAccountTopUp,
+    B2Pochi,
+    StandingOrder,
+    SimSwap,
+    BusinessTransfer,
+}
+
+impl Endpoint {
+    pub fn get_path(&self) -> &'static str {
+        match self {
+            Endpoint::AuthToken => "/oauth/v1/generate?grant_type=client_credentials",
+            Endpoint::RegisterUrl => "/mpesa/c2b/v1/registerurl",
+            Endpoint::RegisterUrlV2 => "/mpesa/c2b/v2/registerurl",
+            Endpoint::BusinessToCustomer => "/mpesa/b2c/v3/paymentrequest",
+            Endpoint::CustomerToBusinessPayment => "/mpesa/stkpush/v1/processrequest",
+            Endpoint::CustomerToBusinessPaymentQuery => "/mpesa/stkpushquery/v1/query",
+            Endpoint::CustomerToBusinessSimulate => "/mpesa/c2b/v1/simulate",
+            Endpoint::BusinessPayBill
+            | Endpoint::BusinessBuyGoods
+            | Endpoint::B2CAccountTopUp
+            | Endpoint::BusinessTransfer => "/mpesa/b2b/v1/paymentrequest",
+            Endpoint::TransactionStatus => "/mpesa/transactionstatus/v1/query",
+            Endpoint::AccountBalance => "/mpesa/accountbalance/v1/query",
+            Endpoint::Reversal => "/mpesa/reversal/v1/request",
+            Endpoint::BusinessExpressCheckout => "/v1/ussdpush/get-msisdn",
+            Endpoint::DynamicQr => "/mpesa/qrcode/v1/generate",
+            Endpoint::RemitTax => "/mpesa/b2b/v1/remittax",
+            Endpoint::BillManagerOptIn => "/v1/billmanager-invoice/optin",
+            Endpoint::BillManagerUpdateOptIn => "/v1/billmanager-invoice/change-optin-details",
+            Endpoint::BillManagerSingleInvoicing => "/v1/billmanager-invoice/single-invoicing",
+            Endpoint::BillManagerBulkInvoicing => "/v1/billmanager-invoice/bulk-invoicing",
+            Endpoint::BillManagerReconciliation => "/v1/billmanager-invoice/reconciliation",
+            Endpoint::BillManagerCancelInvoice => "/v1/billmanager-invoice/cancel-single-invoice",
+            Endpoint::BillManagerCancelBulkInvoices => {
+                "/v1/billmanager-invoice/cancel-bulk-invoices"
+            }
+            Endpoint::PullTransactionsRegister => "/pulltransactions/v1/register",
+            Endpoint::PullTransactionsQuery => "/pulltransactions/v1/query",
+            Endpoint::B2Pochi => "/mpesa/b2pochi/v1/paymentrequest",
+            Endpoint::StandingOrder => "/standingorder/v1/createStandingOrderExternal",
+            Endpoint::SimSwap => "/imsi/v1/checkATI",
+        }
+    }
+}
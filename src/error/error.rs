@@ -0,0 +1,117 @@
+use std::fmt;
+
+use crate::models::models::AUTH_ERROR_CODES;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MpesaError {
+    // An input value was rejected before any request was sent e.g. field "party a",
+    // reason "has invalid value"
+    Validation {
+        field: String,
+        reason: String,
+    },
+    // The request could not be sent or the response could not be read
+    Transport(String),
+    // Daraja answered with an unexpected status code and a body that is not an error response
+    HttpStatus {
+        status: u16,
+        body: String,
+    },
+    // The response body could not be decoded
+    JsonDecode(String),
+    // The access token could not be generated or was rejected
+    Auth(String),
+    // Daraja answered with an error response. The operations return the typed error response
+    // data next to the response data instead (see into_result); register_url, which has no
+    // error response data, returns it as this error.
+    Api {
+        request_id: Option<String>,
+        error_code: Option<String>,
+        error_message: Option<String>,
+    },
+}
+
+impl MpesaError {
+    pub(crate) fn validation(field: &str, reason: &str) -> Self {
+        MpesaError::Validation {
+            field: field.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    // true when daraja rejected the access token, either with status code 401 or with one of
+    // the auth error codes
+    pub fn is_auth_error(&self) -> bool {
+        match self {
+            MpesaError::HttpStatus { status: 401, .. } => true,
+            MpesaError::Api {
+                error_code: Some(x),
+                ..
+            } => AUTH_ERROR_CODES.contains(&x.trim()),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for MpesaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MpesaError::Validation { field, reason } => write!(f, "{} {}", field, reason),
+            MpesaError::Transport(x) => write!(f, "transport error: {}", x),
+            MpesaError::HttpStatus { status, body } => {
+                write!(
+                    f,
+                    "Request failed processing, status code: {}, body: {}",
+                    status, body
+                )
+            }
+            MpesaError::JsonDecode(x) => write!(f, "json decode error: {}", x),
+            MpesaError::Auth(x) => write!(f, "auth error: {}", x),
+            MpesaError::Api {
+                request_id,
+                error_code,
+                error_message,
+            } => {
+                let k = String::from(""); //Default value.
+                write!(
+                    f,
+                    "api error: request id: {}, error code: {}, error message: {}",
+                    request_id.as_ref().unwrap_or(&k),
+                    error_code.as_ref().unwrap_or(&k),
+                    error_message.as_ref().unwrap_or(&k)
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for MpesaError {}
+
+// The operations return Ok((response data, error response data)): a daraja error response is an
+// answer to the request (e.g. an invalid msisdn) and keeps the fields of its operation, while Err
+// is left for requests that got no usable answer. into_result folds the pair into one Result
+// for callers that prefer a single error channel, the error response becomes MpesaError::Api.
+pub fn into_result<T, E>(response: (Option<T>, Option<E>)) -> Result<T, MpesaError>
+where
+    E: Into<MpesaError>,
+{
+    match response {
+        (Some(response_data), _) => Ok(response_data),
+        (None, Some(error_response_data)) => Err(error_response_data.into()),
+        (None, None) => Err(MpesaError::Api {
+            request_id: None,
+            error_code: None,
+            error_message: Some(String::from("response has no data")),
+        }),
+    }
+}
+
+impl From<reqwest::Error> for MpesaError {
+    fn from(_err: reqwest::Error) -> Self {
+        if _err.is_decode() {
+            MpesaError::JsonDecode(_err.to_string())
+        } else {
+            MpesaError::Transport(_err.to_string())
+        }
+    }
+}
@@ -1,6 +1,18 @@
 pub mod models {
     pub mod models;
 }
+pub mod security_credential {
+    pub mod security_credential;
+}
+pub mod error {
+    pub mod error;
+}
+pub mod environment {
+    pub mod environment;
+}
+pub mod client_config {
+    pub mod client_config;
+}
 mod util {
     pub mod util;
 }
@@ -9,6 +21,8 @@ mod authorization {
 }
 mod customer_to_business {
     pub mod customer_to_business_payment;
+    pub mod customer_to_business_payment_query;
+    pub mod customer_to_business_simulate;
     pub mod register_url;
 }
 mod business_to_customer {
@@ -20,59 +34,177 @@ mod business_paybill {
 mod business_buy_goods {
     pub mod business_buy_goods;
 }
+mod transaction_status {
+    pub mod transaction_status;
+}
+mod account_balance {
+    pub mod account_balance;
+}
+mod reversal {
+    pub mod reversal;
+}
+mod business_express_checkout {
+    pub mod business_express_checkout;
+}
+mod dynamic_qr {
+    pub mod dynamic_qr;
+}
+mod tax_remittance {
+    pub mod tax_remittance;
+}
+mod bill_manager {
+    pub mod cancel_invoice;
+    pub mod invoicing;
+    pub mod opt_in;
+    pub mod reconciliation;
+}
+mod pull_transactions {
+    pub mod pull_transactions;
+}
+mod b2c_account_top_up {
+    pub mod b2c_account_top_up;
+}
+mod b2_pochi {
+    pub mod b2_pochi;
+}
+mod standing_order {
+    pub mod standing_order;
+}
+mod sim_swap {
+    pub mod sim_swap;
+}
+mod customer_identity {
+    pub mod customer_identity;
+}
+mod business_transfer {
+    pub mod business_transfer;
+}
 use base64::{
     alphabet,
     engine::{self, general_purpose},
     Engine as _,
 };
+use client_config::client_config::ClientConfig;
+use environment::environment::{Endpoint, Environment};
+use error::error::MpesaError;
 use models::models::{
-    B2CResultParametersOutputDetails, BusinessBuyGoodsErrorResponseData,
+    AccountBalanceErrorResponseData, AccountBalanceInputDetails, AccountBalanceResponseData,
+    AccountBalanceResultParametersOutputDetails, B2CAccountTopUpErrorResponseData,
+    B2CAccountTopUpInputDetails, B2CAccountTopUpResponseData, B2CResultParametersOutputDetails,
+    B2PochiErrorResponseData, B2PochiInputDetails, B2PochiResponseData,
+    B2PochiResultParametersOutputDetails, BillManagerBulkInvoiceInputDetails,
+    BillManagerCancelBulkInvoiceInputDetails, BillManagerCancelInvoiceInputDetails,
+    BillManagerCancelInvoiceResponseData, BillManagerErrorResponseData,
+    BillManagerInvoiceResponseData, BillManagerOptInInputDetails, BillManagerOptInResponseData,
+    BillManagerReconciliationInputDetails, BillManagerReconciliationResponseData,
+    BillManagerSingleInvoiceInputDetails, BusinessBuyGoodsErrorResponseData,
     BusinessBuyGoodsFailedResultParameter, BusinessBuyGoodsInputDetails,
     BusinessBuyGoodsReferenceItem, BusinessBuyGoodsReferenceItemOutputDetails,
     BusinessBuyGoodsResponseData, BusinessBuyGoodsResultParametersOutputDetails,
-    BusinessBuyGoodsTimeoutParametersOutputDetails, BusinessPayBillErrorResponseData,
-    BusinessPayBillFailedResultParameter, BusinessPayBillInputDetails,
-    BusinessPayBillReferenceItem, BusinessPayBillReferenceItemOutputDetails,
-    BusinessPayBillResponseData, BusinessPayBillResultParametersOutputDetails,
-    BusinessPayBillTimeoutParametersOutputDetails, BusinessToCustomerErrorResponseData,
-    BusinessToCustomerInputDetails, BusinessToCustomerResponseData,
-    C2BPaymentResultParametersOutputDetails, CustomerToBusinessPaymentErrorResponseData,
-    CustomerToBusinessPaymentInputDetails, CustomerToBusinessPaymentResponseData, ItemDetails,
-    MixedTypeValue, ReferenceItemDetails, RegisterUrlInputDetails, RegisterUrlResponseData,
-    ResultParameter,
+    BusinessBuyGoodsTimeoutParametersOutputDetails, BusinessExpressCheckoutErrorResponseData,
+    BusinessExpressCheckoutInputDetails, BusinessExpressCheckoutResponseData,
+    BusinessExpressCheckoutResultData, BusinessExpressCheckoutResultOutputDetails,
+    BusinessPayBillErrorResponseData, BusinessPayBillFailedResultParameter,
+    BusinessPayBillInputDetails, BusinessPayBillReferenceItem,
+    BusinessPayBillReferenceItemOutputDetails, BusinessPayBillResponseData,
+    BusinessPayBillResultParametersOutputDetails, BusinessPayBillTimeoutParametersOutputDetails,
+    BusinessToCustomerErrorResponseData, BusinessToCustomerInputDetails,
+    BusinessToCustomerResponseData, BusinessTransferErrorResponseData,
+    BusinessTransferInputDetails, BusinessTransferResponseData,
+    C2BPaymentResultParametersOutputDetails, CustomerIdentityErrorResponseData,
+    CustomerIdentityInputDetails, CustomerIdentityResponseData,
+    CustomerToBusinessPaymentErrorResponseData, CustomerToBusinessPaymentInputDetails,
+    CustomerToBusinessPaymentQueryErrorResponseData, CustomerToBusinessPaymentQueryInputDetails,
+    CustomerToBusinessPaymentQueryResponseData, CustomerToBusinessPaymentResponseData,
+    CustomerToBusinessSimulateErrorResponseData, CustomerToBusinessSimulateInputDetails,
+    CustomerToBusinessSimulateResponseData, DynamicQrErrorResponseData, DynamicQrInputDetails,
+    DynamicQrResponseData, ErrorResponseData, ItemDetails, MixedTypeValue,
+    PullTransactionsErrorResponseData, PullTransactionsQueryAllOutputDetails,
+    PullTransactionsQueryInputDetails, PullTransactionsQueryResponseData,
+    PullTransactionsRegisterInputDetails, PullTransactionsRegisterResponseData,
+    ReferenceItemDetails, RegisterUrlInputDetails, RegisterUrlResponseData,
+    RemitTaxErrorResponseData, RemitTaxInputDetails, RemitTaxResponseData, ResultParameter,
+    ReversalErrorResponseData, ReversalInputDetails, ReversalResponseData,
+    ReversalResultParametersOutputDetails, SimSwapErrorResponseData, SimSwapInputDetails,
+    SimSwapResponseData, StandingOrderCallbackData, StandingOrderCallbackOutputDetails,
+    StandingOrderErrorResponseData, StandingOrderInputDetails, StandingOrderResponseData,
+    TransactionStatusErrorResponseData, TransactionStatusInputDetails,
+    TransactionStatusResponseData, TransactionStatusResultParametersOutputDetails,
 };
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
 
 const AUTHORISATION_BEARER: &str = "Bearer";
+// The cached auth token is refreshed this many seconds before it expires
+const AUTH_TOKEN_EXPIRY_MARGIN_SECS: u64 = 60;
+// Upper bound for pull_transactions_query_all, in case the api keeps returning records
+const PULL_TRANSACTIONS_MAX_PAGES: u32 = 1000;
+
+#[derive(Debug)]
+struct CachedAuthToken {
+    access_token: String,
+    expires_at: Instant,
+}
 
 #[derive(Debug)]
 pub struct MpesaGateway {
     consumer_key: String,
     consumer_secret: String,
-    auth_token_url: String,
+    environment: Environment,
+    auth_token: Mutex<Option<CachedAuthToken>>,
+    client: reqwest::Client,
 }
 
 impl MpesaGateway {
     pub fn new(
         consumer_key: String,
         consumer_secret: String,
-        auth_token_url: String,
-    ) -> Result<Self, String> {
+        environment: Environment,
+    ) -> Result<Self, MpesaError> {
+        Self::new_with_client(
+            consumer_key,
+            consumer_secret,
+            environment,
+            reqwest::Client::new(),
+        )
+    }
+
+    pub fn new_with_client_config(
+        consumer_key: String,
+        consumer_secret: String,
+        environment: Environment,
+        client_config: ClientConfig,
+    ) -> Result<Self, MpesaError> {
+        let client = client_config.build_client()?;
+
+        Self::new_with_client(consumer_key, consumer_secret, environment, client)
+    }
+
+    // The client is shared by all operations, so one that is already used elsewhere in the
+    // application can be passed in to share its connection pool
+    pub fn new_with_client(
+        consumer_key: String,
+        consumer_secret: String,
+        environment: Environment,
+        client: reqwest::Client,
+    ) -> Result<Self, MpesaError> {
         if consumer_key.is_empty() || consumer_key.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("consumer key is empty"));
+            return Err(MpesaError::validation("consumer key", "is empty"));
         }
 
         if consumer_secret.is_empty() || consumer_secret.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("consumer secret is empty"));
+            return Err(MpesaError::validation("consumer secret", "is empty"));
         }
 
-        if auth_token_url.is_empty() || auth_token_url.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("auth_token url is empty"));
-        }
+        environment.validate()?;
 
         Ok(Self {
             consumer_key,
             consumer_secret,
-            auth_token_url,
+            environment,
+            auth_token: Mutex::new(None),
+            client,
         })
     }
 
@@ -93,6 +225,18 @@ impl MpesaGateway {
         api_key
     }
 
+    pub fn get_environment(&self) -> Environment {
+        self.environment.clone()
+    }
+
+    // The per-request api url takes precedence over the environment's endpoint catalog
+    fn get_api_url(&self, api_url: Option<String>, endpoint: Endpoint) -> String {
+        match api_url {
+            Some(x) => x,
+            None => self.environment.get_endpoint_url(endpoint),
+        }
+    }
+
     pub fn get_b2c_result_parameters_output_details(
         &self,
         result_parameters: &ResultParameter,
@@ -229,6 +373,109 @@ impl MpesaGateway {
         b2c_result_parameters_output_details
     }
 
+    pub fn get_b2_pochi_result_parameters_output_details(
+        &self,
+        result_parameters: &ResultParameter,
+    ) -> B2PochiResultParametersOutputDetails {
+        // b2pochi results carry the same parameters as b2c results
+        let b2c_result_parameters_output_details =
+            self.get_b2c_result_parameters_output_details(result_parameters);
+
+        let b2c_keys = [
+            "TransactionAmount",
+            "TransactionReceipt",
+            "B2CRecipientIsRegisteredCustomer",
+            "B2CChargesPaidAccountAvailableFunds",
+            "ReceiverPartyPublicName",
+            "TransactionCompletedDateTime",
+            "B2CUtilityAccountAvailableFunds",
+            "B2CWorkingAccountAvailableFunds",
+        ];
+
+        let mut additional_parameters: Vec<(String, String)> = Vec::new();
+
+        for result_parameter in result_parameters.ResultParameter.iter() {
+            let _key = &result_parameter.Key;
+            let _value = &result_parameter.Value;
+
+            if b2c_keys
+                .iter()
+                .any(|x| _key.to_string().eq_ignore_ascii_case(x))
+            {
+                continue;
+            }
+
+            let _value = match _value {
+                MixedTypeValue::StringValue(s) => s.to_string(),
+                MixedTypeValue::IntegerValue(i) => i.to_string(),
+                MixedTypeValue::FloatValue(f) => f.to_string(),
+            };
+
+            additional_parameters.push((_key.to_string(), _value));
+        }
+
+        B2PochiResultParametersOutputDetails {
+            B2CResultParameters: b2c_result_parameters_output_details,
+            AdditionalParameters: additional_parameters,
+        }
+    }
+
+    pub fn get_standing_order_callback_output_details(
+        &self,
+        callback_data: &StandingOrderCallbackData,
+    ) -> StandingOrderCallbackOutputDetails {
+        let response_code = &callback_data.ResponseHeader.responseCode;
+        let k = String::from(""); //Default value.
+        let response_description = callback_data
+            .ResponseHeader
+            .responseDescription
+            .as_ref()
+            .unwrap_or(&k);
+        let mut transaction_id = String::from("");
+        let mut _status = String::from("");
+        let mut _msisdn = String::from("");
+
+        for response_item in callback_data.ResponseBody.responseData.iter() {
+            let _key = &response_item.name;
+            let _value = &response_item.value;
+
+            // TransactionID
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("TransactionID"))
+            {
+                transaction_id = _value.to_string();
+            }
+
+            // Status
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("Status"))
+            {
+                _status = _value.to_string();
+            }
+
+            // Msisdn
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("Msisdn"))
+            {
+                _msisdn = _value.to_string();
+            }
+        }
+
+        StandingOrderCallbackOutputDetails {
+            ResponseCode: response_code.to_string(),
+            ResponseDescription: response_description.to_string(),
+            TransactionID: transaction_id,
+            Status: _status,
+            Msisdn: _msisdn,
+        }
+    }
+
     pub fn get_c2b_payment_result_parameters_output_details(
         &self,
         list_of_items: &Vec<ItemDetails>,
@@ -773,128 +1020,690 @@ impl MpesaGateway {
         business_buy_goods_timeout_parameters_output_details
     }
 
-    async fn get_auth_token(&self) -> std::result::Result<String, String> {
-        let api_key = self.get_api_key();
+    pub fn get_transaction_status_result_parameters_output_details(
+        &self,
+        result_parameters: &ResultParameter,
+    ) -> TransactionStatusResultParametersOutputDetails {
+        let mut debit_party_name = String::from("");
+        let mut credit_party_name = String::from("");
+        let mut originator_conversation_id = String::from("");
+        let mut initiated_time = String::from("");
+        let mut debit_account_type = String::from("");
+        let mut debit_party_charges = String::from("");
+        let mut transaction_reason = String::from("");
+        let mut reason_type = String::from("");
+        let mut transaction_status = String::from("");
+        let mut finalised_time = String::from("");
+        let mut transaction_amount: f32 = 0.0;
+        let mut conversation_id = String::from("");
+        let mut receipt_no = String::from("");
 
-        let api_url = &self.auth_token_url;
+        for result_parameter in result_parameters.ResultParameter.iter() {
+            let _key = &result_parameter.Key;
+            let _value = &result_parameter.Value;
 
-        let _result =
-            authorization::generate_auth_token::get_auth_token(api_key, api_url.to_string()).await;
+            // DebitPartyName
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("DebitPartyName"))
+            {
+                debit_party_name = match _value {
+                    MixedTypeValue::StringValue(s) => s.to_string(),
+                    MixedTypeValue::IntegerValue(i) => i.to_string(),
+                    MixedTypeValue::FloatValue(f) => f.to_string(),
+                }
+            }
 
-        _result
-    }
+            // CreditPartyName
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("CreditPartyName"))
+            {
+                credit_party_name = match _value {
+                    MixedTypeValue::StringValue(s) => s.to_string(),
+                    MixedTypeValue::IntegerValue(i) => i.to_string(),
+                    MixedTypeValue::FloatValue(f) => f.to_string(),
+                }
+            }
 
-    fn parse_auth_token(&self, access_token_result: String) -> String {
-        let access_token: String = if !access_token_result.is_empty()
-            && access_token_result.replace(" ", "").trim().len() > 0
-        {
-            let mut access_token = AUTHORISATION_BEARER.to_string();
-            let k = " "; // Separator
-            access_token.push_str(k);
-            access_token.push_str(&access_token_result);
+            // OriginatorConversationID
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("OriginatorConversationID"))
+            {
+                originator_conversation_id = match _value {
+                    MixedTypeValue::StringValue(s) => s.to_string(),
+                    MixedTypeValue::IntegerValue(i) => i.to_string(),
+                    MixedTypeValue::FloatValue(f) => f.to_string(),
+                }
+            }
 
-            access_token
-        } else {
-            String::from("")
-        };
+            // InitiatedTime
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("InitiatedTime"))
+            {
+                initiated_time = match _value {
+                    MixedTypeValue::StringValue(s) => s.to_string(),
+                    MixedTypeValue::IntegerValue(i) => i.to_string(),
+                    MixedTypeValue::FloatValue(f) => f.to_string(),
+                }
+            }
 
-        access_token
-    }
+            // DebitAccountType
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("DebitAccountType"))
+            {
+                debit_account_type = match _value {
+                    MixedTypeValue::StringValue(s) => s.to_string(),
+                    MixedTypeValue::IntegerValue(i) => i.to_string(),
+                    MixedTypeValue::FloatValue(f) => f.to_string(),
+                }
+            }
 
-    pub async fn register_url(
-        &self,
-        register_url_details: RegisterUrlInputDetails,
-    ) -> std::result::Result<RegisterUrlResponseData, String> {
-        let _output = self.get_auth_token();
+            // DebitPartyCharges
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("DebitPartyCharges"))
+            {
+                debit_party_charges = match _value {
+                    MixedTypeValue::StringValue(s) => s.to_string(),
+                    MixedTypeValue::IntegerValue(i) => i.to_string(),
+                    MixedTypeValue::FloatValue(f) => f.to_string(),
+                }
+            }
+
+            // TransactionReason
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("TransactionReason"))
+            {
+                transaction_reason = match _value {
+                    MixedTypeValue::StringValue(s) => s.to_string(),
+                    MixedTypeValue::IntegerValue(i) => i.to_string(),
+                    MixedTypeValue::FloatValue(f) => f.to_string(),
+                }
+            }
 
-        let _result = _output.await;
+            // ReasonType
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("ReasonType"))
+            {
+                reason_type = match _value {
+                    MixedTypeValue::StringValue(s) => s.to_string(),
+                    MixedTypeValue::IntegerValue(i) => i.to_string(),
+                    MixedTypeValue::FloatValue(f) => f.to_string(),
+                }
+            }
 
-        match _result {
-            Ok(access_token_result) => {
-                // Handle success case
-                let access_token: String = self.parse_auth_token(access_token_result);
+            // TransactionStatus
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("TransactionStatus"))
+            {
+                transaction_status = match _value {
+                    MixedTypeValue::StringValue(s) => s.to_string(),
+                    MixedTypeValue::IntegerValue(i) => i.to_string(),
+                    MixedTypeValue::FloatValue(f) => f.to_string(),
+                }
+            }
 
-                let _result = customer_to_business::register_url::register_url(
-                    register_url_details,
-                    access_token,
-                )
-                .await;
+            // FinalisedTime
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("FinalisedTime"))
+            {
+                finalised_time = match _value {
+                    MixedTypeValue::StringValue(s) => s.to_string(),
+                    MixedTypeValue::IntegerValue(i) => i.to_string(),
+                    MixedTypeValue::FloatValue(f) => f.to_string(),
+                }
+            }
+
+            // Amount
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("Amount"))
+            {
+                transaction_amount = match _value {
+                    MixedTypeValue::StringValue(s) => s.parse::<f32>().unwrap_or(0.0),
+                    MixedTypeValue::IntegerValue(i) => *i as f32,
+                    MixedTypeValue::FloatValue(f) => *f,
+                }
+            }
 
-                return _result;
+            // ConversationID
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("ConversationID"))
+            {
+                conversation_id = match _value {
+                    MixedTypeValue::StringValue(s) => s.to_string(),
+                    MixedTypeValue::IntegerValue(i) => i.to_string(),
+                    MixedTypeValue::FloatValue(f) => f.to_string(),
+                }
             }
-            Err(_err) => {
-                // Handle error case
-                return Err(_err.to_string());
+
+            // ReceiptNo
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("ReceiptNo"))
+            {
+                receipt_no = match _value {
+                    MixedTypeValue::StringValue(s) => s.to_string(),
+                    MixedTypeValue::IntegerValue(i) => i.to_string(),
+                    MixedTypeValue::FloatValue(f) => f.to_string(),
+                }
             }
         }
+        let transaction_status_result_parameters_output_details =
+            TransactionStatusResultParametersOutputDetails {
+                DebitPartyName: debit_party_name,
+                CreditPartyName: credit_party_name,
+                OriginatorConversationID: originator_conversation_id,
+                InitiatedTime: initiated_time,
+                DebitAccountType: debit_account_type,
+                DebitPartyCharges: debit_party_charges,
+                TransactionReason: transaction_reason,
+                ReasonType: reason_type,
+                TransactionStatus: transaction_status,
+                FinalisedTime: finalised_time,
+                Amount: transaction_amount,
+                ConversationID: conversation_id,
+                ReceiptNo: receipt_no,
+            };
+
+        transaction_status_result_parameters_output_details
     }
 
-    pub async fn b2c(
+    pub fn get_account_balance_result_parameters_output_details(
         &self,
-        business_to_customer_details: BusinessToCustomerInputDetails,
-    ) -> std::result::Result<
-        (
-            Option<BusinessToCustomerResponseData>,
-            Option<BusinessToCustomerErrorResponseData>,
-        ),
-        String,
-    > {
-        let _output = self.get_auth_token();
-
-        let _result = _output.await;
-
-        match _result {
-            Ok(access_token_result) => {
-                // Handle success case
-                let access_token: String = self.parse_auth_token(access_token_result);
+        result_parameters: &ResultParameter,
+    ) -> AccountBalanceResultParametersOutputDetails {
+        let mut account_balance = Vec::new();
+        let mut bo_completed_time = String::from("");
 
-                let _result = business_to_customer::business_to_customer::b2c(
-                    business_to_customer_details,
-                    access_token,
-                )
-                .await;
+        for result_parameter in result_parameters.ResultParameter.iter() {
+            let _key = &result_parameter.Key;
+            let _value = &result_parameter.Value;
 
-                return _result;
+            // AccountBalance
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("AccountBalance"))
+            {
+                account_balance = match _value {
+                    MixedTypeValue::StringValue(s) => util::util::parse_account_balance(s),
+                    _ => Vec::new(),
+                }
             }
-            Err(_err) => {
-                // Handle error case
-                return Err(_err.to_string());
+
+            // BOCompletedTime
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("BOCompletedTime"))
+            {
+                bo_completed_time = match _value {
+                    MixedTypeValue::StringValue(s) => s.to_string(),
+                    MixedTypeValue::IntegerValue(i) => i.to_string(),
+                    MixedTypeValue::FloatValue(f) => f.to_string(),
+                }
             }
         }
+
+        let account_balance_result_parameters_output_details =
+            AccountBalanceResultParametersOutputDetails {
+                AccountBalance: account_balance,
+                BOCompletedTime: bo_completed_time,
+            };
+
+        account_balance_result_parameters_output_details
     }
 
-    pub async fn c2b_payment(
+    pub fn get_reversal_result_parameters_output_details(
         &self,
-        customer_to_business_details: CustomerToBusinessPaymentInputDetails,
-    ) -> std::result::Result<
-        (
+        result_parameters: &ResultParameter,
+    ) -> ReversalResultParametersOutputDetails {
+        let mut debit_account_balance = String::from("");
+        let mut transaction_amount: f32 = 0.0;
+        let mut trans_completed_time = String::from("");
+        let mut original_transaction_id = String::from("");
+        let mut _charge: f32 = 0.0;
+        let mut credit_party_public_name = String::from("");
+        let mut debit_party_public_name = String::from("");
+
+        for result_parameter in result_parameters.ResultParameter.iter() {
+            let _key = &result_parameter.Key;
+            let _value = &result_parameter.Value;
+
+            // DebitAccountBalance
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("DebitAccountBalance"))
+            {
+                debit_account_balance = match _value {
+                    MixedTypeValue::StringValue(s) => s.to_string(),
+                    MixedTypeValue::IntegerValue(i) => i.to_string(),
+                    MixedTypeValue::FloatValue(f) => f.to_string(),
+                }
+            }
+
+            // Amount
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("Amount"))
+            {
+                transaction_amount = match _value {
+                    MixedTypeValue::StringValue(s) => s.parse::<f32>().unwrap_or(0.0),
+                    MixedTypeValue::IntegerValue(i) => *i as f32,
+                    MixedTypeValue::FloatValue(f) => *f,
+                }
+            }
+
+            // TransCompletedTime
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("TransCompletedTime"))
+            {
+                trans_completed_time = match _value {
+                    MixedTypeValue::StringValue(s) => s.to_string(),
+                    MixedTypeValue::IntegerValue(i) => i.to_string(),
+                    MixedTypeValue::FloatValue(f) => f.to_string(),
+                }
+            }
+
+            // OriginalTransactionID
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("OriginalTransactionID"))
+            {
+                original_transaction_id = match _value {
+                    MixedTypeValue::StringValue(s) => s.to_string(),
+                    MixedTypeValue::IntegerValue(i) => i.to_string(),
+                    MixedTypeValue::FloatValue(f) => f.to_string(),
+                }
+            }
+
+            // Charge
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("Charge"))
+            {
+                _charge = match _value {
+                    MixedTypeValue::StringValue(s) => s.parse::<f32>().unwrap_or(0.0),
+                    MixedTypeValue::IntegerValue(i) => *i as f32,
+                    MixedTypeValue::FloatValue(f) => *f,
+                }
+            }
+
+            // CreditPartyPublicName
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("CreditPartyPublicName"))
+            {
+                credit_party_public_name = match _value {
+                    MixedTypeValue::StringValue(s) => s.to_string(),
+                    MixedTypeValue::IntegerValue(i) => i.to_string(),
+                    MixedTypeValue::FloatValue(f) => f.to_string(),
+                }
+            }
+
+            // DebitPartyPublicName
+            if _key
+                .to_string()
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("DebitPartyPublicName"))
+            {
+                debit_party_public_name = match _value {
+                    MixedTypeValue::StringValue(s) => s.to_string(),
+                    MixedTypeValue::IntegerValue(i) => i.to_string(),
+                    MixedTypeValue::FloatValue(f) => f.to_string(),
+                }
+            }
+        }
+        let reversal_result_parameters_output_details = ReversalResultParametersOutputDetails {
+            DebitAccountBalance: debit_account_balance,
+            Amount: transaction_amount,
+            TransCompletedTime: trans_completed_time,
+            OriginalTransactionID: original_transaction_id,
+            Charge: _charge,
+            CreditPartyPublicName: credit_party_public_name,
+            DebitPartyPublicName: debit_party_public_name,
+        };
+
+        reversal_result_parameters_output_details
+    }
+
+    pub fn get_b2b_express_checkout_result_output_details(
+        &self,
+        result_data: &BusinessExpressCheckoutResultData,
+    ) -> Option<BusinessExpressCheckoutResultOutputDetails> {
+        // resultCode "0" means the merchant completed the payment,
+        // any other value means it was cancelled or failed
+        let b2b_express_checkout_result_output_details = if result_data
+            .resultCode
+            .trim()
+            .eq_ignore_ascii_case(&String::from("0"))
+        {
+            let k = String::from(""); //Default value.
+
+            let transaction_amount: f32 = result_data
+                .amount
+                .as_ref()
+                .unwrap_or(&k)
+                .trim()
+                .parse::<f32>()
+                .unwrap_or(0.0);
+            let transaction_id = result_data.transactionId.as_ref().unwrap_or(&k);
+            let conversation_id = result_data.conversationID.as_ref().unwrap_or(&k);
+            let request_id = result_data.requestId.as_ref().unwrap_or(&k);
+            let _status = result_data.status.as_ref().unwrap_or(&k);
+
+            let b2b_express_checkout_result_output_details =
+                BusinessExpressCheckoutResultOutputDetails {
+                    Amount: transaction_amount,
+                    TransactionId: transaction_id.to_string(),
+                    ConversationID: conversation_id.to_string(),
+                    RequestId: request_id.to_string(),
+                    Status: _status.to_string(),
+                };
+
+            Some(b2b_express_checkout_result_output_details)
+        } else {
+            None
+        };
+
+        b2b_express_checkout_result_output_details
+    }
+
+    pub fn get_dynamic_qr_image(&self, qr_code: &str) -> std::result::Result<Vec<u8>, MpesaError> {
+        util::util::decode_qr_code(qr_code)
+    }
+
+    // Returns the cached auth token while it is valid. The lock is held across the refresh so
+    // that concurrent callers share one in-flight request to the oauth endpoint.
+    async fn get_auth_token(&self) -> std::result::Result<String, MpesaError> {
+        let mut cached_auth_token = self.auth_token.lock().await;
+
+        if let Some(x) = cached_auth_token.as_ref() {
+            if Instant::now() < x.expires_at {
+                return Ok(x.access_token.to_string());
+            }
+        }
+
+        let api_key = self.get_api_key();
+
+        let api_url: String = self.environment.get_endpoint_url(Endpoint::AuthToken);
+
+        let auth_token_data =
+            authorization::generate_auth_token::get_auth_token(&self.client, api_key, api_url)
+                .await?;
+
+        let k = String::from(""); //Default value.
+        let access_token = auth_token_data.access_token.as_ref().unwrap_or(&k);
+        let expires_in: u64 = match auth_token_data.expires_in.as_ref() {
+            Some(x) => x.trim().parse::<u64>().unwrap_or(0),
+            None => 0,
+        };
+
+        // A token without a usable expiry is not cached
+        *cached_auth_token =
+            if !access_token.is_empty() && expires_in > AUTH_TOKEN_EXPIRY_MARGIN_SECS {
+                Some(CachedAuthToken {
+                    access_token: access_token.to_string(),
+                    expires_at: Instant::now()
+                        + Duration::from_secs(expires_in - AUTH_TOKEN_EXPIRY_MARGIN_SECS),
+                })
+            } else {
+                None
+            };
+
+        Ok(access_token.to_string())
+    }
+
+    // Drops the cached auth token, unless a concurrent caller has already replaced it
+    async fn invalidate_auth_token(&self, access_token_result: &str) {
+        let mut cached_auth_token = self.auth_token.lock().await;
+
+        let is_rejected_token = match cached_auth_token.as_ref() {
+            Some(x) => x.access_token == access_token_result,
+            None => false,
+        };
+
+        if is_rejected_token {
+            *cached_auth_token = None;
+        }
+    }
+
+    // Sends the request with the current access token. When daraja rejects the token (status
+    // code 401 or an auth error code), the cached token is invalidated and the request is
+    // replayed once with a fresh one.
+    async fn send_with_reauth<R, F, Fut>(
+        &self,
+        send_request: F,
+        is_rejected: fn(&std::result::Result<R, MpesaError>) -> bool,
+    ) -> std::result::Result<R, MpesaError>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = std::result::Result<R, MpesaError>>,
+    {
+        let access_token_result: String = self.get_auth_token().await?;
+
+        let _result = send_request(self.parse_auth_token(access_token_result.to_string())).await;
+
+        if !is_rejected(&_result) {
+            return _result;
+        }
+
+        self.invalidate_auth_token(&access_token_result).await;
+
+        let access_token_result: String = self.get_auth_token().await?;
+
+        let _result = send_request(self.parse_auth_token(access_token_result)).await;
+
+        if is_rejected(&_result) {
+            return Err(MpesaError::Auth(String::from(
+                "access token was rejected again after re-authentication",
+            )));
+        }
+
+        _result
+    }
+
+    fn parse_auth_token(&self, access_token_result: String) -> String {
+        let access_token: String = if !access_token_result.is_empty()
+            && access_token_result.replace(" ", "").trim().len() > 0
+        {
+            let mut access_token = AUTHORISATION_BEARER.to_string();
+            let k = " "; // Separator
+            access_token.push_str(k);
+            access_token.push_str(&access_token_result);
+
+            access_token
+        } else {
+            String::from("")
+        };
+
+        access_token
+    }
+
+    pub async fn register_url(
+        &self,
+        register_url_details: RegisterUrlInputDetails,
+    ) -> std::result::Result<RegisterUrlResponseData, MpesaError> {
+        let api_url: String = self.get_api_url(
+            register_url_details.get_api_url(),
+            register_url_details.get_version().get_endpoint(),
+        );
+
+        self.send_with_reauth(
+            |access_token| {
+                customer_to_business::register_url::register_url(
+                    &self.client,
+                    &register_url_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_result,
+        )
+        .await
+    }
+
+    pub async fn b2c(
+        &self,
+        business_to_customer_details: BusinessToCustomerInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<BusinessToCustomerResponseData>,
+            Option<BusinessToCustomerErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        if let Some(identity_check) = business_to_customer_details.get_identity_check() {
+            self.verify_customer_identity(identity_check).await?;
+        }
+
+        let api_url: String = self.get_api_url(
+            business_to_customer_details.get_api_url(),
+            Endpoint::BusinessToCustomer,
+        );
+
+        self.send_with_reauth(
+            |access_token| {
+                business_to_customer::business_to_customer::b2c(
+                    &self.client,
+                    &business_to_customer_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
+
+    pub async fn c2b_payment(
+        &self,
+        customer_to_business_details: CustomerToBusinessPaymentInputDetails,
+    ) -> std::result::Result<
+        (
             Option<CustomerToBusinessPaymentResponseData>,
             Option<CustomerToBusinessPaymentErrorResponseData>,
         ),
-        String,
+        MpesaError,
     > {
-        let _output = self.get_auth_token();
+        let api_url: String = self.get_api_url(
+            customer_to_business_details.get_api_url(),
+            Endpoint::CustomerToBusinessPayment,
+        );
 
-        let _result = _output.await;
+        self.send_with_reauth(
+            |access_token| {
+                customer_to_business::customer_to_business_payment::c2b_payment(
+                    &self.client,
+                    &customer_to_business_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
 
-        match _result {
-            Ok(access_token_result) => {
-                // Handle success case
-                let access_token: String = self.parse_auth_token(access_token_result);
+    pub async fn c2b_payment_query(
+        &self,
+        customer_to_business_payment_query_details: CustomerToBusinessPaymentQueryInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<CustomerToBusinessPaymentQueryResponseData>,
+            Option<CustomerToBusinessPaymentQueryErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(
+            customer_to_business_payment_query_details.get_api_url(),
+            Endpoint::CustomerToBusinessPaymentQuery,
+        );
 
-                let _result = customer_to_business::customer_to_business_payment::c2b_payment(
-                    customer_to_business_details,
+        self.send_with_reauth(
+            |access_token| {
+                customer_to_business::customer_to_business_payment_query::c2b_payment_query(
+                    &self.client,
+                    &customer_to_business_payment_query_details,
+                    api_url.to_string(),
                     access_token,
                 )
-                .await;
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
 
-                return _result;
-            }
-            Err(_err) => {
-                // Handle error case
-                return Err(_err.to_string());
-            }
+    pub async fn c2b_simulate(
+        &self,
+        customer_to_business_simulate_details: CustomerToBusinessSimulateInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<CustomerToBusinessSimulateResponseData>,
+            Option<CustomerToBusinessSimulateErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        // c2b simulate is only offered by the sandbox
+        let is_production_api_url = match customer_to_business_simulate_details.get_api_url() {
+            Some(x) => environment::environment::is_production_url(&x),
+            None => false,
+        };
+
+        if self.environment.is_production() || is_production_api_url {
+            return Err(MpesaError::validation(
+                "c2b simulate",
+                "is not available in production",
+            ));
         }
+
+        let api_url: String = self.get_api_url(
+            customer_to_business_simulate_details.get_api_url(),
+            Endpoint::CustomerToBusinessSimulate,
+        );
+
+        self.send_with_reauth(
+            |access_token| {
+                customer_to_business::customer_to_business_simulate::c2b_simulate(
+                    &self.client,
+                    &customer_to_business_simulate_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
     }
 
     pub async fn business_paybill(
@@ -905,30 +1714,25 @@ impl MpesaGateway {
             Option<BusinessPayBillResponseData>,
             Option<BusinessPayBillErrorResponseData>,
         ),
-        String,
+        MpesaError,
     > {
-        let _output = self.get_auth_token();
-
-        let _result = _output.await;
-
-        match _result {
-            Ok(access_token_result) => {
-                // Handle success case
-                let access_token: String = self.parse_auth_token(access_token_result);
+        let api_url: String = self.get_api_url(
+            business_paybill_details.get_api_url(),
+            Endpoint::BusinessPayBill,
+        );
 
-                let _result = business_paybill::business_paybill::pay_bill(
-                    business_paybill_details,
+        self.send_with_reauth(
+            |access_token| {
+                business_paybill::business_paybill::pay_bill(
+                    &self.client,
+                    &business_paybill_details,
+                    api_url.to_string(),
                     access_token,
                 )
-                .await;
-
-                return _result;
-            }
-            Err(_err) => {
-                // Handle error case
-                return Err(_err.to_string());
-            }
-        }
+            },
+            is_auth_error_response,
+        )
+        .await
     }
 
     pub async fn business_buy_goods(
@@ -939,88 +1743,2986 @@ impl MpesaGateway {
             Option<BusinessBuyGoodsResponseData>,
             Option<BusinessBuyGoodsErrorResponseData>,
         ),
-        String,
+        MpesaError,
     > {
-        let _output = self.get_auth_token();
+        let api_url: String = self.get_api_url(
+            business_buy_goods_details.get_api_url(),
+            Endpoint::BusinessBuyGoods,
+        );
 
-        let _result = _output.await;
+        self.send_with_reauth(
+            |access_token| {
+                business_buy_goods::business_buy_goods::buy_goods(
+                    &self.client,
+                    &business_buy_goods_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
 
-        match _result {
-            Ok(access_token_result) => {
-                // Handle success case
-                let access_token: String = self.parse_auth_token(access_token_result);
+    pub async fn transaction_status(
+        &self,
+        transaction_status_details: TransactionStatusInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<TransactionStatusResponseData>,
+            Option<TransactionStatusErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(
+            transaction_status_details.get_api_url(),
+            Endpoint::TransactionStatus,
+        );
 
-                let _result = business_buy_goods::business_buy_goods::buy_goods(
-                    business_buy_goods_details,
+        self.send_with_reauth(
+            |access_token| {
+                transaction_status::transaction_status::transaction_status(
+                    &self.client,
+                    &transaction_status_details,
+                    api_url.to_string(),
                     access_token,
                 )
-                .await;
-
-                return _result;
-            }
-            Err(_err) => {
-                // Handle error case
-                return Err(_err.to_string());
-            }
-        }
+            },
+            is_auth_error_response,
+        )
+        .await
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    #[test]
-    fn test_mpesa_gateway() {
-        let consumer_key = String::from("***");
-        let consumer_secret = String::from("***");
-        let auth_token_url = String::from(
-            "https://sandbox.safaricom.co.ke/oauth/v1/generate?grant_type=client_credentials",
+    pub async fn account_balance(
+        &self,
+        account_balance_details: AccountBalanceInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<AccountBalanceResponseData>,
+            Option<AccountBalanceErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(
+            account_balance_details.get_api_url(),
+            Endpoint::AccountBalance,
         );
 
-        let _result = MpesaGateway::new(consumer_key, consumer_secret, auth_token_url);
-        assert_eq!(_result.is_ok(), true);
+        self.send_with_reauth(
+            |access_token| {
+                account_balance::account_balance::account_balance(
+                    &self.client,
+                    &account_balance_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
     }
 
-    #[tokio::test]
-    async fn test_register_url() {
-        let _result = get_register_url_details();
+    pub async fn reversal(
+        &self,
+        reversal_details: ReversalInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<ReversalResponseData>,
+            Option<ReversalErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(reversal_details.get_api_url(), Endpoint::Reversal);
 
-        if let Ok(register_url_details) = _result {
-            let consumer_key = String::from("***");
-            let consumer_secret = String::from("***");
-            let auth_token_url = String::from(
-                "https://sandbox.safaricom.co.ke/oauth/v1/generate?grant_type=client_credentials",
-            );
+        self.send_with_reauth(
+            |access_token| {
+                reversal::reversal::reversal(
+                    &self.client,
+                    &reversal_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
+
+    pub async fn b2b_express_checkout(
+        &self,
+        business_express_checkout_details: BusinessExpressCheckoutInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<BusinessExpressCheckoutResponseData>,
+            Option<BusinessExpressCheckoutErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(
+            business_express_checkout_details.get_api_url(),
+            Endpoint::BusinessExpressCheckout,
+        );
 
-            let _result = MpesaGateway::new(consumer_key, consumer_secret, auth_token_url);
+        self.send_with_reauth(
+            |access_token| {
+                business_express_checkout::business_express_checkout::express_checkout(
+                    &self.client,
+                    &business_express_checkout_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
 
-            if let Ok(mpesa_gateway) = _result {
-                // Initiate the request through the sdk
-                let _output = mpesa_gateway.register_url(register_url_details);
+    pub async fn dynamic_qr(
+        &self,
+        dynamic_qr_details: DynamicQrInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<DynamicQrResponseData>,
+            Option<DynamicQrErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String =
+            self.get_api_url(dynamic_qr_details.get_api_url(), Endpoint::DynamicQr);
+
+        self.send_with_reauth(
+            |access_token| {
+                dynamic_qr::dynamic_qr::generate_qr(
+                    &self.client,
+                    &dynamic_qr_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
 
-                let _result: std::result::Result<RegisterUrlResponseData, String> = _output.await;
+    pub async fn remit_tax(
+        &self,
+        remit_tax_details: RemitTaxInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<RemitTaxResponseData>,
+            Option<RemitTaxErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(remit_tax_details.get_api_url(), Endpoint::RemitTax);
 
-                assert_eq!(_result.is_ok(), true);
+        self.send_with_reauth(
+            |access_token| {
+                tax_remittance::tax_remittance::remit_tax(
+                    &self.client,
+                    &remit_tax_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
+
+    pub async fn bill_manager_opt_in(
+        &self,
+        bill_manager_opt_in_details: BillManagerOptInInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<BillManagerOptInResponseData>,
+            Option<BillManagerErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(
+            bill_manager_opt_in_details.get_api_url(),
+            Endpoint::BillManagerOptIn,
+        );
+
+        self.send_with_reauth(
+            |access_token| {
+                bill_manager::opt_in::opt_in(
+                    &self.client,
+                    &bill_manager_opt_in_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
+
+    pub async fn bill_manager_update_opt_in(
+        &self,
+        bill_manager_opt_in_details: BillManagerOptInInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<BillManagerOptInResponseData>,
+            Option<BillManagerErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(
+            bill_manager_opt_in_details.get_api_url(),
+            Endpoint::BillManagerUpdateOptIn,
+        );
+
+        self.send_with_reauth(
+            |access_token| {
+                bill_manager::opt_in::update_opt_in(
+                    &self.client,
+                    &bill_manager_opt_in_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
+
+    pub async fn bill_manager_single_invoicing(
+        &self,
+        bill_manager_single_invoice_details: BillManagerSingleInvoiceInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<BillManagerInvoiceResponseData>,
+            Option<BillManagerErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(
+            bill_manager_single_invoice_details.get_api_url(),
+            Endpoint::BillManagerSingleInvoicing,
+        );
+
+        self.send_with_reauth(
+            |access_token| {
+                bill_manager::invoicing::single_invoicing(
+                    &self.client,
+                    &bill_manager_single_invoice_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
+
+    pub async fn bill_manager_bulk_invoicing(
+        &self,
+        bill_manager_bulk_invoice_details: BillManagerBulkInvoiceInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<BillManagerInvoiceResponseData>,
+            Option<BillManagerErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(
+            bill_manager_bulk_invoice_details.get_api_url(),
+            Endpoint::BillManagerBulkInvoicing,
+        );
+
+        self.send_with_reauth(
+            |access_token| {
+                bill_manager::invoicing::bulk_invoicing(
+                    &self.client,
+                    &bill_manager_bulk_invoice_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
+
+    pub async fn bill_manager_reconciliation(
+        &self,
+        bill_manager_reconciliation_details: BillManagerReconciliationInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<BillManagerReconciliationResponseData>,
+            Option<BillManagerErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(
+            bill_manager_reconciliation_details.get_api_url(),
+            Endpoint::BillManagerReconciliation,
+        );
+
+        self.send_with_reauth(
+            |access_token| {
+                bill_manager::reconciliation::reconciliation(
+                    &self.client,
+                    &bill_manager_reconciliation_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
+
+    pub async fn cancel_invoice(
+        &self,
+        bill_manager_cancel_invoice_details: BillManagerCancelInvoiceInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<BillManagerCancelInvoiceResponseData>,
+            Option<BillManagerErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(
+            bill_manager_cancel_invoice_details.get_api_url(),
+            Endpoint::BillManagerCancelInvoice,
+        );
+
+        self.send_with_reauth(
+            |access_token| {
+                bill_manager::cancel_invoice::cancel_single_invoice(
+                    &self.client,
+                    &bill_manager_cancel_invoice_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
+
+    pub async fn cancel_bulk_invoices(
+        &self,
+        bill_manager_cancel_bulk_invoice_details: BillManagerCancelBulkInvoiceInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<BillManagerCancelInvoiceResponseData>,
+            Option<BillManagerErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(
+            bill_manager_cancel_bulk_invoice_details.get_api_url(),
+            Endpoint::BillManagerCancelBulkInvoices,
+        );
+
+        self.send_with_reauth(
+            |access_token| {
+                bill_manager::cancel_invoice::cancel_bulk_invoices(
+                    &self.client,
+                    &bill_manager_cancel_bulk_invoice_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
+
+    pub async fn pull_transactions_register(
+        &self,
+        pull_transactions_register_details: PullTransactionsRegisterInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<PullTransactionsRegisterResponseData>,
+            Option<PullTransactionsErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(
+            pull_transactions_register_details.get_api_url(),
+            Endpoint::PullTransactionsRegister,
+        );
+
+        self.send_with_reauth(
+            |access_token| {
+                pull_transactions::pull_transactions::register(
+                    &self.client,
+                    &pull_transactions_register_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
+
+    pub async fn pull_transactions_query(
+        &self,
+        pull_transactions_query_details: PullTransactionsQueryInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<PullTransactionsQueryResponseData>,
+            Option<PullTransactionsErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(
+            pull_transactions_query_details.get_api_url(),
+            Endpoint::PullTransactionsQuery,
+        );
+
+        self.send_with_reauth(
+            |access_token| {
+                pull_transactions::pull_transactions::query(
+                    &self.client,
+                    &pull_transactions_query_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
+
+    // Runs pull_transactions_query repeatedly, moving the offset forward by the number of
+    // records received, until a page comes back empty or repeats the previous page.
+    // A failed page or the page limit stops the paging, the records received before it are
+    // returned with the error and the offset to resume from.
+    pub async fn pull_transactions_query_all(
+        &self,
+        pull_transactions_query_details: PullTransactionsQueryInputDetails,
+    ) -> PullTransactionsQueryAllOutputDetails {
+        let api_url: Option<String> = pull_transactions_query_details.get_api_url();
+        let short_code: String = pull_transactions_query_details.get_short_code();
+        let start_date: String = pull_transactions_query_details.get_start_date();
+        let end_date: String = pull_transactions_query_details.get_end_date();
+        let mut output_details = PullTransactionsQueryAllOutputDetails {
+            records: Vec::new(),
+            offset_value: pull_transactions_query_details.get_offset_value(),
+            error_response_data: None,
+            error: None,
+        };
+        let mut previous_transaction_id: Option<String> = None;
+        let mut page_count: u32 = 0;
+
+        loop {
+            if page_count == PULL_TRANSACTIONS_MAX_PAGES {
+                output_details.error = Some(MpesaError::Api {
+                    request_id: None,
+                    error_code: None,
+                    error_message: Some(format!(
+                        "pull transactions query returned more than {} pages",
+                        PULL_TRANSACTIONS_MAX_PAGES
+                    )),
+                });
+                break;
+            }
+
+            page_count += 1;
+
+            let page_details = PullTransactionsQueryInputDetails::new(
+                api_url.clone(),
+                short_code.to_string(),
+                start_date.to_string(),
+                end_date.to_string(),
+                output_details.offset_value,
+            );
+
+            let _result = match page_details {
+                Ok(page_details) => self.pull_transactions_query(page_details).await,
+                Err(e) => Err(e),
+            };
+
+            let pull_transactions_query_response_data = match _result {
+                Ok((_, Some(pull_transactions_error_response_data))) => {
+                    output_details.error_response_data =
+                        Some(pull_transactions_error_response_data);
+                    break;
+                }
+                Ok((pull_transactions_query_response_data, None)) => {
+                    pull_transactions_query_response_data
+                }
+                Err(e) => {
+                    output_details.error = Some(e);
+                    break;
+                }
+            };
+
+            let page_records = match pull_transactions_query_response_data {
+                Some(response_data) => response_data.get_records(),
+                None => Vec::new(),
             };
+
+            if page_records.is_empty() {
+                break;
+            }
+
+            // the offset was not applied, the same page would be returned again
+            let transaction_id = page_records[0].transactionId.to_string();
+            if previous_transaction_id.as_ref() == Some(&transaction_id) {
+                break;
+            }
+            previous_transaction_id = Some(transaction_id);
+
+            output_details.offset_value += page_records.len() as u32;
+            output_details.records.extend(page_records);
         }
+
+        output_details
     }
 
-    fn get_register_url_details() -> Result<RegisterUrlInputDetails, String> {
-        let api_url = String::from("https://sandbox.safaricom.co.ke/mpesa/c2b/v1/registerurl");
-        let short_code = String::from("60***");
-        let response_type = String::from("Completed");
-        let confirmation_url = String::from("***");
-        let validation_url = String::from("***");
+    pub async fn b2c_account_top_up(
+        &self,
+        b2c_account_top_up_details: B2CAccountTopUpInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<B2CAccountTopUpResponseData>,
+            Option<B2CAccountTopUpErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(
+            b2c_account_top_up_details.get_api_url(),
+            Endpoint::B2CAccountTopUp,
+        );
 
-        let _result = RegisterUrlInputDetails::new(
-            api_url,
-            short_code,
-            response_type,
-            confirmation_url,
-            validation_url,
+        self.send_with_reauth(
+            |access_token| {
+                b2c_account_top_up::b2c_account_top_up::top_up(
+                    &self.client,
+                    &b2c_account_top_up_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
+
+    pub async fn b2_pochi(
+        &self,
+        b2_pochi_details: B2PochiInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<B2PochiResponseData>,
+            Option<B2PochiErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(b2_pochi_details.get_api_url(), Endpoint::B2Pochi);
+
+        self.send_with_reauth(
+            |access_token| {
+                b2_pochi::b2_pochi::pay_to_pochi(
+                    &self.client,
+                    &b2_pochi_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
+
+    pub async fn create_standing_order(
+        &self,
+        standing_order_details: StandingOrderInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<StandingOrderResponseData>,
+            Option<StandingOrderErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(
+            standing_order_details.get_api_url(),
+            Endpoint::StandingOrder,
         );
 
-        _result
+        self.send_with_reauth(
+            |access_token| {
+                standing_order::standing_order::create_standing_order(
+                    &self.client,
+                    &standing_order_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
+
+    pub async fn check_sim_swap(
+        &self,
+        sim_swap_details: SimSwapInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<SimSwapResponseData>,
+            Option<SimSwapErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(sim_swap_details.get_api_url(), Endpoint::SimSwap);
+
+        self.send_with_reauth(
+            |access_token| {
+                sim_swap::sim_swap::check_sim_swap(
+                    &self.client,
+                    &sim_swap_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
+
+    pub async fn check_customer_identity(
+        &self,
+        customer_identity_details: CustomerIdentityInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<CustomerIdentityResponseData>,
+            Option<CustomerIdentityErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = customer_identity_details.get_api_url();
+
+        self.send_with_reauth(
+            |access_token| {
+                customer_identity::customer_identity::check_identity(
+                    &self.client,
+                    &customer_identity_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
+
+    async fn verify_customer_identity(
+        &self,
+        customer_identity_details: CustomerIdentityInputDetails,
+    ) -> std::result::Result<(), MpesaError> {
+        let _result = self
+            .check_customer_identity(customer_identity_details)
+            .await?;
+
+        match _result {
+            (Some(customer_identity_response_data), _) => {
+                if customer_identity_response_data.is_match() {
+                    return Ok(());
+                }
+
+                return Err(MpesaError::validation(
+                    "customer identity",
+                    "does not match",
+                ));
+            }
+            (None, Some(customer_identity_error_response_data)) => {
+                return Err(MpesaError::from(customer_identity_error_response_data));
+            }
+            (None, None) => {
+                return Err(MpesaError::Api {
+                    request_id: None,
+                    error_code: None,
+                    error_message: Some(String::from("customer identity check failed")),
+                });
+            }
+        }
+    }
+
+    pub async fn business_transfer(
+        &self,
+        business_transfer_details: BusinessTransferInputDetails,
+    ) -> std::result::Result<
+        (
+            Option<BusinessTransferResponseData>,
+            Option<BusinessTransferErrorResponseData>,
+        ),
+        MpesaError,
+    > {
+        let api_url: String = self.get_api_url(
+            business_transfer_details.get_api_url(),
+            Endpoint::BusinessTransfer,
+        );
+
+        self.send_with_reauth(
+            |access_token| {
+                business_transfer::business_transfer::transfer(
+                    &self.client,
+                    &business_transfer_details,
+                    api_url.to_string(),
+                    access_token,
+                )
+            },
+            is_auth_error_response,
+        )
+        .await
+    }
+}
+
+fn is_auth_error_response<T, E: ErrorResponseData>(
+    _result: &std::result::Result<(Option<T>, Option<E>), MpesaError>,
+) -> bool {
+    match _result {
+        Ok((_, Some(x))) => x.is_auth_error(),
+        Ok(_) => false,
+        Err(_err) => _err.is_auth_error(),
+    }
+}
+
+fn is_auth_error_result<T>(_result: &std::result::Result<T, MpesaError>) -> bool {
+    match _result {
+        Ok(_) => false,
+        Err(_err) => _err.is_auth_error(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use models::models::{
+        BillManagerInvoiceDetails, BillManagerInvoiceItemDetails, BusinessTransferCommand,
+        C2bMsisdn, CustomerIdType, RegisterUrlVersion, ResultParameterDetails, SimSwapResponseBody,
+        SimSwapResponseHeader, StandingOrderFrequency,
+    };
+    use sha2::{Digest, Sha256};
+    use std::io::{Read, Write};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    // Local stand-in for daraja. Every request is answered with the (status, body) returned by
+    // handler, which receives the raw request. Returns the base url and the request counter.
+    fn start_mock_server<F>(handler: F) -> (String, Arc<AtomicUsize>)
+    where
+        F: Fn(&str) -> (u16, String) + Send + 'static,
+    {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("mock server");
+        let base_url = format!("http://{}", listener.local_addr().expect("local addr"));
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&hits);
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(x) => x,
+                    Err(_) => continue,
+                };
+
+                let mut request: Vec<u8> = Vec::new();
+                let mut buffer = [0u8; 4096];
+                loop {
+                    let n = stream.read(&mut buffer).unwrap_or(0);
+                    request.extend_from_slice(&buffer[..n]);
+
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    let header_end = match text.find("\r\n\r\n") {
+                        Some(x) => x + 4,
+                        None if n > 0 => continue,
+                        None => break,
+                    };
+                    let content_length: usize = text
+                        .lines()
+                        .find(|x| x.to_lowercase().starts_with("content-length:"))
+                        .and_then(|x| x[15..].trim().parse().ok())
+                        .unwrap_or(0);
+                    if n == 0 || request.len() >= header_end + content_length {
+                        break;
+                    }
+                }
+
+                counter.fetch_add(1, Ordering::SeqCst);
+                let (status, body) = handler(&String::from_utf8_lossy(&request));
+                let response = format!(
+                    "HTTP/1.1 {} OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        (base_url, hits)
+    }
+
+    #[test]
+    fn test_mpesa_gateway() {
+        let consumer_key = String::from("***");
+        let consumer_secret = String::from("***");
+        let _result = MpesaGateway::new(consumer_key, consumer_secret, Environment::Sandbox);
+        assert_eq!(_result.is_ok(), true);
+    }
+
+    #[test]
+    fn test_environment_endpoint_url() {
+        assert_eq!(
+            Environment::Sandbox.get_endpoint_url(Endpoint::AuthToken),
+            "https://sandbox.safaricom.co.ke/oauth/v1/generate?grant_type=client_credentials"
+        );
+        assert_eq!(
+            Environment::Production.get_endpoint_url(Endpoint::BusinessToCustomer),
+            "https://api.safaricom.co.ke/mpesa/b2c/v3/paymentrequest"
+        );
+        assert_eq!(
+            Environment::Custom(String::from("http://localhost:8080/"))
+                .get_endpoint_url(Endpoint::CustomerToBusinessPayment),
+            "http://localhost:8080/mpesa/stkpush/v1/processrequest"
+        );
+
+        // the register url version picks the endpoint
+        let register_url_details = RegisterUrlInputDetails::new(
+            None,
+            String::from("600984"),
+            String::from("Completed"),
+            String::from("https://mydomain.com/confirmation"),
+            String::from("https://mydomain.com/validation"),
+        )
+        .expect("register url details");
+        assert_eq!(
+            Environment::Sandbox
+                .get_endpoint_url(register_url_details.get_version().get_endpoint()),
+            "https://sandbox.safaricom.co.ke/mpesa/c2b/v1/registerurl"
+        );
+
+        let register_url_details = register_url_details.with_version(RegisterUrlVersion::V2);
+        assert_eq!(
+            Environment::Sandbox
+                .get_endpoint_url(register_url_details.get_version().get_endpoint()),
+            "https://sandbox.safaricom.co.ke/mpesa/c2b/v2/registerurl"
+        );
+
+        let mpesa_gateway = MpesaGateway::new(
+            String::from("***"),
+            String::from("***"),
+            Environment::Custom(String::from("http://localhost:8080")),
+        )
+        .expect("mpesa gateway");
+
+        // a per-request api url takes precedence over the endpoint catalog
+        assert_eq!(
+            mpesa_gateway.get_api_url(None, Endpoint::AccountBalance),
+            "http://localhost:8080/mpesa/accountbalance/v1/query"
+        );
+        assert_eq!(
+            mpesa_gateway.get_api_url(
+                Some(String::from("http://localhost:9090/query")),
+                Endpoint::AccountBalance
+            ),
+            "http://localhost:9090/query"
+        );
+
+        let _result = MpesaGateway::new(
+            String::from("***"),
+            String::from("***"),
+            Environment::Custom(String::from("localhost:8080")),
+        );
+        assert_eq!(_result.is_err(), true);
+    }
+
+    #[tokio::test]
+    async fn test_auth_token_caching() {
+        let (base_url, hits) = start_mock_server(|_request| {
+            (
+                200,
+                String::from(r#"{"access_token":"token1","expires_in":"3599"}"#),
+            )
+        });
+
+        let mpesa_gateway = MpesaGateway::new(
+            String::from("***"),
+            String::from("***"),
+            Environment::Custom(base_url),
+        )
+        .expect("mpesa gateway");
+
+        // concurrent callers share one request to the oauth endpoint
+        let (first, second) = tokio::join!(
+            mpesa_gateway.get_auth_token(),
+            mpesa_gateway.get_auth_token()
+        );
+        assert_eq!(first, Ok(String::from("token1")));
+        assert_eq!(second, Ok(String::from("token1")));
+
+        let _result = mpesa_gateway.get_auth_token().await;
+        assert_eq!(_result, Ok(String::from("token1")));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // a token that expires within the refresh margin is not cached
+        let (base_url, hits) = start_mock_server(|_request| {
+            (
+                200,
+                String::from(r#"{"access_token":"token2","expires_in":"30"}"#),
+            )
+        });
+
+        let mpesa_gateway = MpesaGateway::new(
+            String::from("***"),
+            String::from("***"),
+            Environment::Custom(base_url),
+        )
+        .expect("mpesa gateway");
+
+        let _result = mpesa_gateway.get_auth_token().await;
+        assert_eq!(_result, Ok(String::from("token2")));
+        let _result = mpesa_gateway.get_auth_token().await;
+        assert_eq!(_result, Ok(String::from("token2")));
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_reauthentication_on_rejected_token() {
+        // every oauth request hands out a new token, only "token2" is accepted by the api
+        let get_handler = |accepted_token: &'static str| {
+            let tokens = AtomicUsize::new(0);
+
+            move |request: &str| {
+                if request.starts_with("GET /oauth/v1/generate") {
+                    let token = tokens.fetch_add(1, Ordering::SeqCst) + 1;
+                    return (
+                        200,
+                        format!(r#"{{"access_token":"token{}","expires_in":"3599"}}"#, token),
+                    );
+                }
+
+                if request.contains(&format!("Bearer {}", accepted_token)) {
+                    return (
+                        200,
+                        String::from(
+                            r#"{"ResponseHeader":{"responseRefID":"4568-4a1f-96a8-3b2d0ed6d7e5","responseCode":"200","responseDesc":"Success"},"ResponseBody":{"customerNumber":"254708374149","lastSwapDate":null}}"#,
+                        ),
+                    );
+                }
+
+                (
+                    401,
+                    String::from(
+                        r#"{"requestId":"11728-2929992-1","errorCode":"404.001.03","errorMessage":"Invalid Access Token"}"#,
+                    ),
+                )
+            }
+        };
+
+        let (base_url, hits) = start_mock_server(get_handler("token2"));
+
+        let mpesa_gateway = MpesaGateway::new(
+            String::from("***"),
+            String::from("***"),
+            Environment::Custom(base_url),
+        )
+        .expect("mpesa gateway");
+
+        let sim_swap_details =
+            SimSwapInputDetails::new(None, 254708374149).expect("sim swap details");
+
+        // token1 is rejected, the request is replayed once with token2
+        let _result = mpesa_gateway.check_sim_swap(sim_swap_details).await;
+        assert_eq!(_result.is_ok(), true);
+        assert_eq!(_result.expect("sim swap").0.is_some(), true);
+        assert_eq!(hits.load(Ordering::SeqCst), 4);
+
+        let (base_url, hits) = start_mock_server(get_handler("token3"));
+
+        let mpesa_gateway = MpesaGateway::new(
+            String::from("***"),
+            String::from("***"),
+            Environment::Custom(base_url),
+        )
+        .expect("mpesa gateway");
+
+        let sim_swap_details =
+            SimSwapInputDetails::new(None, 254708374149).expect("sim swap details");
+
+        // the replay is not repeated when the fresh token is rejected as well
+        let _result = mpesa_gateway.check_sim_swap(sim_swap_details).await;
+        assert_eq!(
+            _result.err(),
+            Some(MpesaError::Auth(String::from(
+                "access token was rejected again after re-authentication"
+            )))
+        );
+        assert_eq!(hits.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_reauthentication_on_auth_error_code() {
+        // daraja answers with status code 404 and an auth error code, only the token in
+        // accepted_token is accepted by the api
+        let get_handler = |accepted_token: &'static str| {
+            let tokens = AtomicUsize::new(0);
+
+            move |request: &str| {
+                if request.starts_with("GET /oauth/v1/generate") {
+                    let token = tokens.fetch_add(1, Ordering::SeqCst) + 1;
+                    return (
+                        200,
+                        format!(r#"{{"access_token":"token{}","expires_in":"3599"}}"#, token),
+                    );
+                }
+
+                if !request.contains(&format!("Bearer {}", accepted_token)) {
+                    return (
+                        404,
+                        String::from(
+                            r#"{"requestId":"11728-2929992-1","errorCode":"404.001.03","errorMessage":"Invalid Access Token"}"#,
+                        ),
+                    );
+                }
+
+                if request.starts_with("POST /mpesa/c2b/v1/registerurl") {
+                    return (
+                        200,
+                        String::from(
+                            r#"{"OriginatorCoversationID":"6e86-45dd-91ac-fd5d4178ab523408729","ResponseCode":"0","ResponseDescription":"Success"}"#,
+                        ),
+                    );
+                }
+
+                (
+                    200,
+                    String::from(
+                        r#"{"ConversationID":"AG_20191219_00005797af5d7d75f652","OriginatorConversationID":"16740-34861180-1","ResponseCode":"0","ResponseDescription":"Accept the service request successfully."}"#,
+                    ),
+                )
+            }
+        };
+
+        let get_register_url_details = || {
+            RegisterUrlInputDetails::new(
+                None,
+                String::from("600984"),
+                String::from("Completed"),
+                String::from("https://mydomain.com/confirmation"),
+                String::from("https://mydomain.com/validation"),
+            )
+            .expect("register url details")
+        };
+
+        let get_business_to_customer_details = || {
+            BusinessToCustomerInputDetails::new(
+                None,
+                String::from("feb5e3f2-fbbc-4745-844c-ee37b546f627"),
+                String::from("testapi"),
+                String::from("***"),
+                String::from("BusinessPayment"),
+                10,
+                600996,
+                String::from("254708374149"),
+                String::from("Test remarks"),
+                String::from("https://mydomain.com/b2c/queue"),
+                String::from("https://mydomain.com/b2c/result"),
+                String::from(""),
+            )
+            .expect("business to customer details")
+        };
+
+        // token1 is rejected, the requests are replayed once with token2
+        let (base_url, hits) = start_mock_server(get_handler("token2"));
+
+        let mpesa_gateway = MpesaGateway::new(
+            String::from("***"),
+            String::from("***"),
+            Environment::Custom(base_url),
+        )
+        .expect("mpesa gateway");
+
+        let _result = mpesa_gateway
+            .register_url(get_register_url_details())
+            .await
+            .expect("register url");
+        assert_eq!(_result.ResponseCode, Some(String::from("0")));
+        assert_eq!(hits.load(Ordering::SeqCst), 4);
+
+        let (base_url, hits) = start_mock_server(get_handler("token2"));
+
+        let mpesa_gateway = MpesaGateway::new(
+            String::from("***"),
+            String::from("***"),
+            Environment::Custom(base_url),
+        )
+        .expect("mpesa gateway");
+
+        let _result = mpesa_gateway
+            .b2c(get_business_to_customer_details())
+            .await
+            .expect("b2c");
+        assert_eq!(_result.0.is_some(), true);
+        assert_eq!(_result.1.is_none(), true);
+        assert_eq!(hits.load(Ordering::SeqCst), 4);
+
+        // the fresh token is rejected as well
+        let (base_url, hits) = start_mock_server(get_handler("token3"));
+
+        let mpesa_gateway = MpesaGateway::new(
+            String::from("***"),
+            String::from("***"),
+            Environment::Custom(base_url),
+        )
+        .expect("mpesa gateway");
+
+        let _result = mpesa_gateway.register_url(get_register_url_details()).await;
+        assert_eq!(
+            _result.err(),
+            Some(MpesaError::Auth(String::from(
+                "access token was rejected again after re-authentication"
+            )))
+        );
+        assert_eq!(hits.load(Ordering::SeqCst), 4);
+
+        let (base_url, hits) = start_mock_server(get_handler("token3"));
+
+        let mpesa_gateway = MpesaGateway::new(
+            String::from("***"),
+            String::from("***"),
+            Environment::Custom(base_url),
+        )
+        .expect("mpesa gateway");
+
+        let _result = mpesa_gateway.b2c(get_business_to_customer_details()).await;
+        assert_eq!(
+            _result.err(),
+            Some(MpesaError::Auth(String::from(
+                "access token was rejected again after re-authentication"
+            )))
+        );
+        assert_eq!(hits.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_register_url_reauthentication_on_unauthorized_status() {
+        // daraja answers with status code 401 and a json body whose error code is not one of
+        // the auth error codes, only the token in accepted_token is accepted by the api
+        let get_handler = |accepted_token: &'static str| {
+            let tokens = AtomicUsize::new(0);
+
+            move |request: &str| {
+                if request.starts_with("GET /oauth/v1/generate") {
+                    let token = tokens.fetch_add(1, Ordering::SeqCst) + 1;
+                    return (
+                        200,
+                        format!(r#"{{"access_token":"token{}","expires_in":"3599"}}"#, token),
+                    );
+                }
+
+                if !request.contains(&format!("Bearer {}", accepted_token)) {
+                    return (
+                        401,
+                        String::from(
+                            r#"{"requestId":"11728-2929992-1","errorCode":"401.003.01","errorMessage":"Error Occurred - Unauthorized"}"#,
+                        ),
+                    );
+                }
+
+                (
+                    200,
+                    String::from(
+                        r#"{"OriginatorCoversationID":"6e86-45dd-91ac-fd5d4178ab523408729","ResponseCode":"0","ResponseDescription":"Success"}"#,
+                    ),
+                )
+            }
+        };
+
+        let get_register_url_details = || {
+            RegisterUrlInputDetails::new(
+                None,
+                String::from("600984"),
+                String::from("Completed"),
+                String::from("https://mydomain.com/confirmation"),
+                String::from("https://mydomain.com/validation"),
+            )
+            .expect("register url details")
+        };
+
+        // token1 is rejected, the request is replayed once with token2
+        let (base_url, hits) = start_mock_server(get_handler("token2"));
+
+        let mpesa_gateway = MpesaGateway::new(
+            String::from("***"),
+            String::from("***"),
+            Environment::Custom(base_url),
+        )
+        .expect("mpesa gateway");
+
+        let _result = mpesa_gateway
+            .register_url(get_register_url_details())
+            .await
+            .expect("register url");
+        assert_eq!(_result.ResponseCode, Some(String::from("0")));
+        assert_eq!(hits.load(Ordering::SeqCst), 4);
+
+        // the fresh token is rejected as well
+        let (base_url, hits) = start_mock_server(get_handler("token3"));
+
+        let mpesa_gateway = MpesaGateway::new(
+            String::from("***"),
+            String::from("***"),
+            Environment::Custom(base_url),
+        )
+        .expect("mpesa gateway");
+
+        let _result = mpesa_gateway.register_url(get_register_url_details()).await;
+        assert_eq!(
+            _result.err(),
+            Some(MpesaError::Auth(String::from(
+                "access token was rejected again after re-authentication"
+            )))
+        );
+        assert_eq!(hits.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_client_config() {
+        let _result = ClientConfig::new().with_proxy(String::from("not a proxy url"));
+        assert_eq!(_result.is_err(), true);
+
+        let _result = ClientConfig::new().with_root_certificate(b"not a certificate");
+        assert_eq!(_result.is_err(), true);
+
+        let _result = ClientConfig::new().with_root_certificate(include_bytes!(
+            "security_credential/test_data/test_certificate.pem"
+        ));
+        assert_eq!(_result.is_ok(), true);
+
+        // the gateway sends every request through the configured client
+        let (base_url, hits) = start_mock_server(|request| {
+            if request
+                .to_lowercase()
+                .contains("user-agent: mpesa-system/1.0")
+            {
+                return (
+                    200,
+                    String::from(r#"{"access_token":"token1","expires_in":"3599"}"#),
+                );
+            }
+
+            (400, String::from("{}"))
+        });
+
+        let client_config = ClientConfig::new()
+            .with_connect_timeout(Duration::from_secs(5))
+            .with_request_timeout(Duration::from_secs(30))
+            .with_pool_max_idle_per_host(4)
+            .with_user_agent(String::from("mpesa-system/1.0"))
+            .expect("client config");
+
+        let mpesa_gateway = MpesaGateway::new_with_client_config(
+            String::from("***"),
+            String::from("***"),
+            Environment::Custom(base_url.to_string()),
+            client_config,
+        )
+        .expect("mpesa gateway");
+
+        let _result = mpesa_gateway.get_auth_token().await;
+        assert_eq!(_result, Ok(String::from("token1")));
+
+        // a pre-built client is used as is
+        let client = reqwest::Client::builder()
+            .user_agent("mpesa-system/1.0")
+            .build()
+            .expect("client");
+
+        let mpesa_gateway = MpesaGateway::new_with_client(
+            String::from("***"),
+            String::from("***"),
+            Environment::Custom(base_url),
+            client,
+        )
+        .expect("mpesa gateway");
+
+        let _result = mpesa_gateway.get_auth_token().await;
+        assert_eq!(_result, Ok(String::from("token1")));
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_mpesa_error() {
+        let _result =
+            MpesaGateway::new(String::from(" "), String::from("***"), Environment::Sandbox);
+        let _err = _result.err().expect("validation error");
+        assert_eq!(
+            _err,
+            MpesaError::Validation {
+                field: String::from("consumer key"),
+                reason: String::from("is empty"),
+            }
+        );
+        assert_eq!(_err.to_string(), "consumer key is empty");
+
+        let _result = SimSwapInputDetails::new(None, 0);
+        assert!(matches!(_result, Err(MpesaError::Validation { .. })));
+
+        // a body that is not an error response is kept with the status code
+        let (base_url, _hits) = start_mock_server(|request: &str| {
+            if request.starts_with("GET /oauth/v1/generate") {
+                return (
+                    200,
+                    String::from(r#"{"access_token":"token1","expires_in":"3599"}"#),
+                );
+            }
+
+            if request.contains("254708374149") {
+                return (503, String::from("Service Unavailable"));
+            }
+
+            (
+                400,
+                String::from(
+                    r#"{"requestId":"11728-2929992-1","errorCode":"400.002.02","errorMessage":"Bad Request - Invalid MSISDN"}"#,
+                ),
+            )
+        });
+
+        let mpesa_gateway = MpesaGateway::new(
+            String::from("***"),
+            String::from("***"),
+            Environment::Custom(base_url),
+        )
+        .expect("mpesa gateway");
+
+        let sim_swap_details =
+            SimSwapInputDetails::new(None, 254708374149).expect("sim swap details");
+
+        let _result = mpesa_gateway.check_sim_swap(sim_swap_details).await;
+        assert_eq!(
+            _result.err(),
+            Some(MpesaError::HttpStatus {
+                status: 503,
+                body: String::from("Service Unavailable"),
+            })
+        );
+
+        // daraja error responses are still returned as data and convert into MpesaError::Api
+        let sim_swap_details =
+            SimSwapInputDetails::new(None, 254708374150).expect("sim swap details");
+
+        let _result = mpesa_gateway.check_sim_swap(sim_swap_details).await;
+        let sim_swap_data = _result.expect("sim swap data");
+        assert_eq!(sim_swap_data.0.is_none(), true);
+        assert_eq!(
+            error::error::into_result(sim_swap_data).err(),
+            Some(MpesaError::Api {
+                request_id: Some(String::from("11728-2929992-1")),
+                error_code: Some(String::from("400.002.02")),
+                error_message: Some(String::from("Bad Request - Invalid MSISDN")),
+            })
+        );
+
+        let sim_swap_data: (Option<u8>, Option<SimSwapErrorResponseData>) = (Some(1), None);
+        assert_eq!(error::error::into_result(sim_swap_data), Ok(1));
+
+        let sim_swap_data: (Option<u8>, Option<SimSwapErrorResponseData>) = (None, None);
+        assert_eq!(
+            error::error::into_result(sim_swap_data).err(),
+            Some(MpesaError::Api {
+                request_id: None,
+                error_code: None,
+                error_message: Some(String::from("response has no data")),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_register_url() {
+        let _result = get_register_url_details();
+
+        if let Ok(register_url_details) = _result {
+            let consumer_key = String::from("***");
+            let consumer_secret = String::from("***");
+            let _result = MpesaGateway::new(consumer_key, consumer_secret, Environment::Sandbox);
+
+            if let Ok(mpesa_gateway) = _result {
+                // Initiate the request through the sdk
+                let _output = mpesa_gateway.register_url(register_url_details);
+
+                let _result: std::result::Result<RegisterUrlResponseData, MpesaError> =
+                    _output.await;
+
+                assert_eq!(_result.is_ok(), true);
+            };
+        }
+    }
+
+    fn get_register_url_details() -> Result<RegisterUrlInputDetails, MpesaError> {
+        let api_url = Some(String::from(
+            "https://sandbox.safaricom.co.ke/mpesa/c2b/v1/registerurl",
+        ));
+        let short_code = String::from("60***");
+        let response_type = String::from("Completed");
+        let confirmation_url = String::from("***");
+        let validation_url = String::from("***");
+
+        let _result = RegisterUrlInputDetails::new(
+            api_url,
+            short_code,
+            response_type,
+            confirmation_url,
+            validation_url,
+        );
+
+        _result
+    }
+
+    #[test]
+    fn test_c2b_payment_query_input_details() {
+        let api_url = Some(String::from(
+            "https://sandbox.safaricom.co.ke/mpesa/stkpushquery/v1/query",
+        ));
+        let business_short_code = String::from("174379");
+        let _password = String::from("***");
+        let time_stamp = String::from("20230901120000");
+
+        let _result = CustomerToBusinessPaymentQueryInputDetails::new(
+            api_url.clone(),
+            business_short_code.to_string(),
+            _password.to_string(),
+            time_stamp.to_string(),
+            String::from("ws_CO_01092023120000000000"),
+        );
+        assert_eq!(_result.is_ok(), true);
+
+        let _result = CustomerToBusinessPaymentQueryInputDetails::new(
+            api_url,
+            business_short_code,
+            _password,
+            time_stamp,
+            String::from(" "),
+        );
+        assert_eq!(_result.is_err(), true);
+    }
+
+    #[test]
+    fn test_remit_tax_input_details() {
+        let get_input_details = |account_reference: &str| {
+            RemitTaxInputDetails::new(
+                None,
+                String::from("testapi"),
+                String::from("***"),
+                239,
+                String::from("600978"),
+                account_reference.to_string(),
+                String::from("OK"),
+                String::from("https://mydomain.com/remittax/queue/"),
+                String::from("https://mydomain.com/remittax/result/"),
+            )
+        };
+
+        let get_remit_tax_data = |remit_tax_details: &RemitTaxInputDetails| {
+            let remit_tax_data = util::util::build_remit_tax_data(
+                remit_tax_details.get_initiator(),
+                remit_tax_details.get_security_credential(),
+                remit_tax_details.get_command_id(),
+                remit_tax_details.get_sender_identifier_type(),
+                remit_tax_details.get_reciever_identifier_type(),
+                remit_tax_details.get_amount(),
+                remit_tax_details.get_party_a(),
+                remit_tax_details.get_party_b(),
+                remit_tax_details.get_account_reference(),
+                remit_tax_details.get_remarks(),
+                remit_tax_details.get_queue_time_out_url(),
+                remit_tax_details.get_result_url(),
+            );
+
+            serde_json::to_value(&remit_tax_data).expect("remit tax data")
+        };
+
+        // the PRN is trimmed
+        let remit_tax_details = get_input_details(" 2024051234567 ").expect("remit tax details");
+        let remit_tax_data = get_remit_tax_data(&remit_tax_details);
+        assert_eq!(remit_tax_data["AccountReference"], "2024051234567");
+        assert_eq!(remit_tax_data["CommandID"], "PayTaxToKRA");
+        assert_eq!(remit_tax_data["SenderIdentifierType"], "4");
+        assert_eq!(remit_tax_data["RecieverIdentifierType"], "4");
+        assert_eq!(remit_tax_data["PartyA"], "600978");
+        assert_eq!(remit_tax_data["PartyB"], "572572");
+
+        let _result = get_input_details(" ");
+        assert_eq!(
+            _result.err(),
+            Some(MpesaError::validation("account reference", "is empty"))
+        );
+
+        for account_reference in ["PRN-2024051", "20240512345678"] {
+            let _result = get_input_details(account_reference);
+            assert_eq!(
+                _result.err(),
+                Some(MpesaError::validation(
+                    "account reference",
+                    "has invalid prn"
+                ))
+            );
+        }
+
+        // the KRA defaults can be overridden
+        let remit_tax_details = get_input_details("2024051234567")
+            .and_then(|x| x.with_party_b(String::from("572573")))
+            .and_then(|x| x.with_identifier_types(String::from("4"), String::from("2")))
+            .expect("remit tax details");
+        let remit_tax_data = get_remit_tax_data(&remit_tax_details);
+        assert_eq!(remit_tax_data["PartyB"], "572573");
+        assert_eq!(remit_tax_data["SenderIdentifierType"], "4");
+        assert_eq!(remit_tax_data["RecieverIdentifierType"], "2");
+
+        let _result =
+            get_input_details("2024051234567").and_then(|x| x.with_party_b(String::from("5725")));
+        assert_eq!(
+            _result.err(),
+            Some(MpesaError::validation("party b", "has invalid value"))
+        );
+
+        let _result = get_input_details("2024051234567")
+            .and_then(|x| x.with_identifier_types(String::from(" "), String::from("4")));
+        assert_eq!(
+            _result.err(),
+            Some(MpesaError::validation("sender identifier type", "is empty"))
+        );
+    }
+
+    #[test]
+    fn test_reversal_input_details() {
+        let get_input_details = |command_id: &str,
+                                 _amount: u32,
+                                 receiver_party: &str,
+                                 reciever_identifier_type: &str| {
+            ReversalInputDetails::new(
+                Some(String::from(
+                    "https://sandbox.safaricom.co.ke/mpesa/reversal/v1/request",
+                )),
+                String::from("testapi"),
+                String::from("***"),
+                command_id.to_string(),
+                String::from("OEI2AK4Q16"),
+                _amount,
+                receiver_party.to_string(),
+                reciever_identifier_type.to_string(),
+                String::from("https://mydomain.com/reversal/result"),
+                String::from("https://mydomain.com/reversal/queue"),
+                String::from("Wrong recipient"),
+                String::from(""),
+            )
+        };
+
+        let _result = get_input_details("TransactionReversal", 100, "600992", "11");
+        assert_eq!(_result.is_ok(), true);
+
+        // only organization short codes can receive a reversal
+        let _result = get_input_details("TransactionReversal", 100, "600992", "4");
+        assert_eq!(
+            _result.err(),
+            Some(MpesaError::validation(
+                "reciever identifier type",
+                "has invalid value"
+            ))
+        );
+
+        let _result = get_input_details("TransactionReversal", 100, "600992", " ");
+        assert_eq!(
+            _result.err(),
+            Some(MpesaError::validation(
+                "reciever identifier type",
+                "is empty"
+            ))
+        );
+
+        let _result = get_input_details("BusinessPayment", 100, "600992", "11");
+        assert_eq!(
+            _result.err(),
+            Some(MpesaError::validation("command id", "has invalid value"))
+        );
+
+        let _result = get_input_details("TransactionReversal", 0, "600992", "11");
+        assert_eq!(
+            _result.err(),
+            Some(MpesaError::validation("amount", "has invalid value"))
+        );
+
+        let _result = get_input_details("TransactionReversal", 100, "6009921", "11");
+        assert_eq!(
+            _result.err(),
+            Some(MpesaError::validation(
+                "receiver party",
+                "has invalid value"
+            ))
+        );
+
+        let _result = ReversalInputDetails::new(
+            None,
+            String::from("testapi"),
+            String::from("***"),
+            String::from("TransactionReversal"),
+            String::from(" "),
+            100,
+            String::from("600992"),
+            String::from("11"),
+            String::from("https://mydomain.com/reversal/result"),
+            String::from("https://mydomain.com/reversal/queue"),
+            String::from("Wrong recipient"),
+            String::from(""),
+        );
+        assert_eq!(
+            _result.err(),
+            Some(MpesaError::validation("transaction id", "is empty"))
+        );
+
+        let _result = ReversalInputDetails::new(
+            None,
+            String::from("testapi"),
+            String::from("***"),
+            String::from("TransactionReversal"),
+            String::from("OEI2AK4Q16"),
+            100,
+            String::from("600992"),
+            String::from("11"),
+            String::from("https://mydomain.com/reversal/result"),
+            String::from("https://mydomain.com/reversal/queue"),
+            String::from(""),
+            String::from(""),
+        );
+        assert_eq!(
+            _result.err(),
+            Some(MpesaError::validation("remarks", "is empty"))
+        );
+    }
+
+    #[test]
+    fn test_get_reversal_result_parameters_output_details() {
+        let result_data: models::models::ReversalResultData = serde_json::from_str(
+            r#"{"Result":{"ResultType":0,"ResultCode":0,"ResultDesc":"The service request is processed successfully.","OriginatorConversationID":"8521-4298025-1","ConversationID":"AG_20181005_00004d7ee675c0c7ee0b","TransactionID":"MJ561H6X5O","ResultParameters":{"ResultParameter":[{"Key":"DebitAccountBalance","Value":"Utility Account|KES|51661.00|51661.00|0.00|0.00"},{"Key":"Amount","Value":100},{"Key":"TransCompletedTime","Value":"20181005153225"},{"Key":"OriginalTransactionID","Value":"MJ551H6X5D"},{"Key":"Charge","Value":0.5},{"Key":"CreditPartyPublicName","Value":"254708374149 - John Doe"},{"Key":"DebitPartyPublicName","Value":"601315 - Safaricom1338"}]},"ReferenceData":{"ReferenceItem":{"Key":"QueueTimeoutURL","Value":"https://internalsandbox.safaricom.co.ke/mpesa/reversalresults/v1/submit"}}}}"#,
+        )
+        .expect("reversal result data");
+
+        let consumer_key = String::from("***");
+        let consumer_secret = String::from("***");
+        let mpesa_gateway = MpesaGateway::new(consumer_key, consumer_secret, Environment::Sandbox)
+            .expect("mpesa gateway");
+
+        let reversal_result_parameters_output_details = mpesa_gateway
+            .get_reversal_result_parameters_output_details(&result_data.Result.ResultParameters);
+
+        assert_eq!(
+            reversal_result_parameters_output_details.DebitAccountBalance,
+            "Utility Account|KES|51661.00|51661.00|0.00|0.00"
+        );
+        assert_eq!(reversal_result_parameters_output_details.Amount, 100.0);
+        assert_eq!(
+            reversal_result_parameters_output_details.TransCompletedTime,
+            "20181005153225"
+        );
+        assert_eq!(
+            reversal_result_parameters_output_details.OriginalTransactionID,
+            "MJ551H6X5D"
+        );
+        assert_eq!(reversal_result_parameters_output_details.Charge, 0.5);
+        assert_eq!(
+            reversal_result_parameters_output_details.CreditPartyPublicName,
+            "254708374149 - John Doe"
+        );
+        assert_eq!(
+            reversal_result_parameters_output_details.DebitPartyPublicName,
+            "601315 - Safaricom1338"
+        );
+    }
+
+    #[test]
+    fn test_get_transaction_status_result_parameters_output_details() {
+        let result_parameters = ResultParameter {
+            ResultParameter: vec![
+                ResultParameterDetails {
+                    Key: String::from("DebitPartyName"),
+                    Value: MixedTypeValue::StringValue(String::from("600310 - Safaricom 333")),
+                },
+                ResultParameterDetails {
+                    Key: String::from("TransactionStatus"),
+                    Value: MixedTypeValue::StringValue(String::from("Completed")),
+                },
+                ResultParameterDetails {
+                    Key: String::from("Amount"),
+                    Value: MixedTypeValue::IntegerValue(300),
+                },
+                ResultParameterDetails {
+                    Key: String::from("ReceiptNo"),
+                    Value: MixedTypeValue::StringValue(String::from("NLJ11HAY8V")),
+                },
+            ],
+        };
+
+        let consumer_key = String::from("***");
+        let consumer_secret = String::from("***");
+        let mpesa_gateway = MpesaGateway::new(consumer_key, consumer_secret, Environment::Sandbox)
+            .expect("mpesa gateway");
+
+        let transaction_status_result_parameters_output_details = mpesa_gateway
+            .get_transaction_status_result_parameters_output_details(&result_parameters);
+
+        assert_eq!(
+            transaction_status_result_parameters_output_details.DebitPartyName,
+            "600310 - Safaricom 333"
+        );
+        assert_eq!(
+            transaction_status_result_parameters_output_details.TransactionStatus,
+            "Completed"
+        );
+        assert_eq!(
+            transaction_status_result_parameters_output_details.Amount,
+            300.0
+        );
+        assert_eq!(
+            transaction_status_result_parameters_output_details.ReceiptNo,
+            "NLJ11HAY8V"
+        );
+    }
+
+    #[test]
+    fn test_get_account_balance_result_parameters_output_details() {
+        let result_parameters = ResultParameter {
+            ResultParameter: vec![
+                ResultParameterDetails {
+                    Key: String::from("AccountBalance"),
+                    Value: MixedTypeValue::StringValue(String::from(
+                        "Working Account|KES|700000.00|700000.00|0.00|0.00&Float Account|KES|0.00|0.00|0.00|0.00&Utility Account|KES|228037.00|228037.00|0.00|0.00",
+                    )),
+                },
+                ResultParameterDetails {
+                    Key: String::from("BOCompletedTime"),
+                    Value: MixedTypeValue::StringValue(String::from("20200109125710")),
+                },
+            ],
+        };
+
+        let consumer_key = String::from("***");
+        let consumer_secret = String::from("***");
+        let mpesa_gateway = MpesaGateway::new(consumer_key, consumer_secret, Environment::Sandbox)
+            .expect("mpesa gateway");
+
+        let account_balance_result_parameters_output_details =
+            mpesa_gateway.get_account_balance_result_parameters_output_details(&result_parameters);
+
+        let accounts = &account_balance_result_parameters_output_details.AccountBalance;
+        assert_eq!(accounts.len(), 3);
+        assert_eq!(accounts[0].AccountName, "Working Account");
+        assert_eq!(accounts[0].Currency, "KES");
+        assert_eq!(accounts[0].CurrentBalance, 700000.00);
+        assert_eq!(accounts[2].AccountName, "Utility Account");
+        assert_eq!(accounts[2].AvailableBalance, 228037.00);
+        assert_eq!(accounts[2].ReservedAmount, 0.00);
+        assert_eq!(
+            account_balance_result_parameters_output_details.BOCompletedTime,
+            "20200109125710"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_c2b_simulate_production() {
+        let api_url = Some(String::from(
+            "https://api.safaricom.co.ke/mpesa/c2b/v1/simulate",
+        ));
+        let short_code = String::from("600984");
+        let command_id = String::from("CustomerPayBillOnline");
+        let _amount: u32 = 1;
+        let _msisdn: u64 = 254708374149;
+        let bill_ref_number = String::from("Test");
+
+        let customer_to_business_simulate_details = CustomerToBusinessSimulateInputDetails::new(
+            api_url,
+            short_code,
+            command_id,
+            _amount,
+            _msisdn,
+            bill_ref_number,
+        )
+        .expect("c2b simulate details");
+
+        let consumer_key = String::from("***");
+        let consumer_secret = String::from("***");
+        let mpesa_gateway = MpesaGateway::new(consumer_key, consumer_secret, Environment::Sandbox)
+            .expect("mpesa gateway");
+
+        let _result = mpesa_gateway
+            .c2b_simulate(customer_to_business_simulate_details)
+            .await;
+
+        assert_eq!(_result.is_err(), true);
+
+        // the endpoint catalog of a production gateway is rejected as well
+        let customer_to_business_simulate_details = CustomerToBusinessSimulateInputDetails::new(
+            None,
+            String::from("600984"),
+            String::from("CustomerPayBillOnline"),
+            1,
+            254708374149,
+            String::from("Test"),
+        )
+        .expect("c2b simulate details");
+
+        let mpesa_gateway = MpesaGateway::new(
+            String::from("***"),
+            String::from("***"),
+            Environment::Production,
+        )
+        .expect("mpesa gateway");
+
+        let _result = mpesa_gateway
+            .c2b_simulate(customer_to_business_simulate_details)
+            .await;
+
+        assert_eq!(_result.is_err(), true);
+
+        // a custom environment or api url that points at production is rejected
+        for base_url in [
+            "https://api.safaricom.co.ke/",
+            "HTTPS://API.SAFARICOM.CO.KE",
+            "http://api.safaricom.co.ke:443/mpesa",
+            "https://Api.Safaricom.co.ke./",
+        ] {
+            assert_eq!(
+                Environment::Custom(base_url.to_string()).is_production(),
+                true
+            );
+
+            let mpesa_gateway = MpesaGateway::new(
+                String::from("***"),
+                String::from("***"),
+                Environment::Custom(base_url.to_string()),
+            )
+            .expect("mpesa gateway");
+
+            let customer_to_business_simulate_details =
+                CustomerToBusinessSimulateInputDetails::new(
+                    None,
+                    String::from("600984"),
+                    String::from("CustomerPayBillOnline"),
+                    1,
+                    254708374149,
+                    String::from("Test"),
+                )
+                .expect("c2b simulate details");
+
+            let _result = mpesa_gateway
+                .c2b_simulate(customer_to_business_simulate_details)
+                .await;
+            assert_eq!(
+                _result.err(),
+                Some(MpesaError::validation(
+                    "c2b simulate",
+                    "is not available in production"
+                ))
+            );
+        }
+
+        let customer_to_business_simulate_details = CustomerToBusinessSimulateInputDetails::new(
+            Some(String::from(
+                "HTTPS://API.Safaricom.co.ke/mpesa/c2b/v1/simulate",
+            )),
+            String::from("600984"),
+            String::from("CustomerPayBillOnline"),
+            1,
+            254708374149,
+            String::from("Test"),
+        )
+        .expect("c2b simulate details");
+
+        let mpesa_gateway = MpesaGateway::new(
+            String::from("***"),
+            String::from("***"),
+            Environment::Sandbox,
+        )
+        .expect("mpesa gateway");
+
+        let _result = mpesa_gateway
+            .c2b_simulate(customer_to_business_simulate_details)
+            .await;
+        assert_eq!(
+            _result.err(),
+            Some(MpesaError::validation(
+                "c2b simulate",
+                "is not available in production"
+            ))
+        );
+
+        assert_eq!(
+            Environment::Custom(String::from("https://sandbox.safaricom.co.ke")).is_production(),
+            false
+        );
+        assert_eq!(
+            Environment::Custom(String::from("http://localhost:8080/api.safaricom.co.ke"))
+                .is_production(),
+            false
+        );
+    }
+
+    #[test]
+    fn test_get_b2b_express_checkout_result_output_details() {
+        let consumer_key = String::from("***");
+        let consumer_secret = String::from("***");
+        let mpesa_gateway = MpesaGateway::new(consumer_key, consumer_secret, Environment::Sandbox)
+            .expect("mpesa gateway");
+
+        let result_data = BusinessExpressCheckoutResultData {
+            resultCode: String::from("0"),
+            resultDesc: String::from("The service request is processed successfully."),
+            requestId: Some(String::from("404e1aec-19e0-4ce3-973d-bd92e94c8021")),
+            amount: Some(String::from("71.0")),
+            resultType: Some(String::from("0")),
+            conversationID: Some(String::from("AG_20230426_2010434680d9f5a73766")),
+            transactionId: Some(String::from("RDQ01NFT1Q")),
+            status: Some(String::from("SUCCESS")),
+            paymentReference: None,
+        };
+
+        let _result = mpesa_gateway.get_b2b_express_checkout_result_output_details(&result_data);
+        let b2b_express_checkout_result_output_details = _result.expect("output details");
+        assert_eq!(b2b_express_checkout_result_output_details.Amount, 71.0);
+        assert_eq!(
+            b2b_express_checkout_result_output_details.TransactionId,
+            "RDQ01NFT1Q"
+        );
+
+        let result_data = BusinessExpressCheckoutResultData {
+            resultCode: String::from("4001"),
+            resultDesc: String::from("User cancelled transaction"),
+            requestId: Some(String::from("c2a9ba32-9e11-4b90-892c-7bc54944609a")),
+            amount: Some(String::from("71.0")),
+            resultType: None,
+            conversationID: None,
+            transactionId: None,
+            status: None,
+            paymentReference: Some(String::from("MAndbubry3hi")),
+        };
+
+        let _result = mpesa_gateway.get_b2b_express_checkout_result_output_details(&result_data);
+        assert_eq!(_result.is_none(), true);
+    }
+
+    #[test]
+    fn test_dynamic_qr_input_details() {
+        let api_url = Some(String::from(
+            "https://sandbox.safaricom.co.ke/mpesa/qrcode/v1/generate",
+        ));
+
+        let _result = DynamicQrInputDetails::new(
+            api_url.clone(),
+            String::from("TEST SUPERMARKET"),
+            String::from("Invoice Test"),
+            1,
+            String::from("BG"),
+            String::from("373132"),
+            String::from("300"),
+        );
+        assert_eq!(_result.is_ok(), true);
+
+        // SM expects a msisdn, not a till number
+        let _result = DynamicQrInputDetails::new(
+            api_url,
+            String::from("TEST SUPERMARKET"),
+            String::from("Invoice Test"),
+            1,
+            String::from("SM"),
+            String::from("373132"),
+            String::from("300"),
+        );
+        assert_eq!(_result.is_err(), true);
+    }
+
+    #[test]
+    fn test_get_dynamic_qr_image() {
+        let consumer_key = String::from("***");
+        let consumer_secret = String::from("***");
+        let mpesa_gateway = MpesaGateway::new(consumer_key, consumer_secret, Environment::Sandbox)
+            .expect("mpesa gateway");
+
+        // png signature followed by a single byte
+        let _result = mpesa_gateway.get_dynamic_qr_image("iVBORw0KGgoA");
+        assert_eq!(
+            _result,
+            Ok(vec![0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00])
+        );
+
+        let _result = mpesa_gateway.get_dynamic_qr_image("aGVsbG8=");
+        assert_eq!(_result.is_err(), true);
+    }
+
+    #[test]
+    fn test_bill_manager_bulk_invoice_input_details() {
+        let api_url = Some(String::from(
+            "https://sandbox.safaricom.co.ke/v1/billmanager-invoice/bulk-invoicing",
+        ));
+
+        let get_invoice = |external_reference: &str| {
+            let invoice_items = vec![
+                BillManagerInvoiceItemDetails::new(String::from("food"), 700)
+                    .expect("invoice item"),
+            ];
+
+            BillManagerInvoiceDetails::new(
+                external_reference.to_string(),
+                String::from("John Doe"),
+                String::from("0722000000"),
+                String::from("August 2021"),
+                String::from("Jentrys"),
+                String::from("2021-10-12"),
+                String::from("1ASD678H"),
+                800,
+                invoice_items,
+            )
+            .expect("invoice")
+        };
+
+        let _result = BillManagerBulkInvoiceInputDetails::new(
+            api_url.clone(),
+            vec![get_invoice("#9932340"), get_invoice("#9932341")],
+        );
+        assert_eq!(_result.is_ok(), true);
+
+        let _result = BillManagerBulkInvoiceInputDetails::new(
+            api_url.clone(),
+            vec![get_invoice("#9932340"), get_invoice("#9932340")],
+        );
+        assert_eq!(_result.is_err(), true);
+
+        let invoices = (0..1001).map(|x| get_invoice(&format!("#{}", x))).collect();
+        let _result = BillManagerBulkInvoiceInputDetails::new(api_url, invoices);
+        assert_eq!(_result.is_err(), true);
+    }
+
+    #[test]
+    fn test_bill_manager_cancel_bulk_invoice_input_details() {
+        let api_url = Some(String::from(
+            "https://sandbox.safaricom.co.ke/v1/billmanager-invoice/cancel-bulk-invoices",
+        ));
+
+        let _result = BillManagerCancelBulkInvoiceInputDetails::new(
+            api_url.clone(),
+            vec![String::from("#9932340"), String::from("#9932341")],
+        );
+        assert_eq!(_result.is_ok(), true);
+
+        let _result = BillManagerCancelBulkInvoiceInputDetails::new(api_url.clone(), vec![]);
+        assert_eq!(_result.is_err(), true);
+
+        let _result = BillManagerCancelBulkInvoiceInputDetails::new(
+            api_url,
+            vec![String::from("#9932340"), String::from(" ")],
+        );
+        assert_eq!(_result.is_err(), true);
+    }
+
+    #[test]
+    fn test_pull_transactions_query_response_data() {
+        let api_url = Some(String::from(
+            "https://sandbox.safaricom.co.ke/pulltransactions/v1/query",
+        ));
+
+        let _result = PullTransactionsQueryInputDetails::new(
+            api_url.clone(),
+            String::from("600000"),
+            String::from("2020-08-04 08:36:00"),
+            String::from("2020-08-16 10:10:00"),
+            0,
+        );
+        assert_eq!(_result.is_ok(), true);
+
+        let _result = PullTransactionsQueryInputDetails::new(
+            api_url,
+            String::from("600000"),
+            String::from("2020-08-16 10:10:00"),
+            String::from("2020-08-04 08:36:00"),
+            0,
+        );
+        assert_eq!(_result.is_err(), true);
+
+        let response_data = PullTransactionsQueryResponseData {
+            ResponseRefID: Some(String::from("18633-7271215-1")),
+            ResponseCode: Some(String::from("1000")),
+            ResponseMessage: Some(String::from("Success")),
+            Response: None,
+        };
+        assert_eq!(response_data.get_records().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_pull_transactions_query_all() {
+        let get_page = |records: &[(&str, &str)]| {
+            let records: Vec<String> = records
+                .iter()
+                .map(|(transaction_id, amount)| {
+                    format!(
+                        r#"{{"transactionId":"{}","trxDate":"2020-08-05T10:12:23Z","msisdn":254722000000,"sender":"UNKNOWN","transactiontype":"c2b-pay-bill-debit","billreference":"INV-001","amount":{},"organizationname":"Test Org"}}"#,
+                        transaction_id, amount
+                    )
+                })
+                .collect();
+
+            format!(
+                r#"{{"ResponseRefID":"18633-7271215-1","ResponseCode":"1000","ResponseMessage":"Success","Response":[[{}]]}}"#,
+                records.join(",")
+            )
+        };
+
+        // "paged" applies the offset, "repeat" ignores the offset and "endless" never runs out of
+        // records. "error", "decode" and "unauthorized" fail the second page with an error
+        // response, an invalid body and a 401.
+        let get_handler = move |mode: &'static str| {
+            move |request: &str| {
+                if request.starts_with("GET /oauth/v1/generate") {
+                    return (
+                        200,
+                        String::from(r#"{"access_token":"token1","expires_in":"3599"}"#),
+                    );
+                }
+
+                if mode == "endless" {
+                    let offset_value: String = request
+                        .split(r#""OffSetValue":""#)
+                        .nth(1)
+                        .and_then(|x| x.split('"').next())
+                        .unwrap_or_default()
+                        .to_string();
+
+                    return (200, get_page(&[(&offset_value, "1")]));
+                }
+
+                if request.contains(r#""OffSetValue":"0""#) || mode == "repeat" {
+                    return (
+                        200,
+                        get_page(&[("OHR5HNL2P6", "\"10.0\""), ("OHR5HNL2P7", "250")]),
+                    );
+                }
+
+                if request.contains(r#""OffSetValue":"2""#) {
+                    match mode {
+                        "error" => {
+                            return (
+                                500,
+                                String::from(
+                                    r#"{"requestId":"11728-2929992-1","errorCode":"500.003.02","errorMessage":"System is busy. Please try again in few minutes."}"#,
+                                ),
+                            );
+                        }
+                        "decode" => {
+                            return (200, String::from("<html>Service Unavailable</html>"));
+                        }
+                        "unauthorized" => {
+                            return (401, String::from(""));
+                        }
+                        _ => {}
+                    }
+
+                    return (200, get_page(&[("OHR5HNL2P8", "\"35.5\"")]));
+                }
+
+                (
+                    200,
+                    String::from(
+                        r#"{"ResponseRefID":"18633-7271215-1","ResponseCode":"1000","ResponseMessage":"Success","Response":[[]]}"#,
+                    ),
+                )
+            }
+        };
+
+        let get_input_details = || {
+            PullTransactionsQueryInputDetails::new(
+                None,
+                String::from("600000"),
+                String::from("2020-08-04 08:36:00"),
+                String::from("2020-08-16 10:10:00"),
+                0,
+            )
+            .expect("pull transactions query details")
+        };
+
+        let get_mpesa_gateway = |base_url: String| {
+            MpesaGateway::new(
+                String::from("***"),
+                String::from("***"),
+                Environment::Custom(base_url),
+            )
+            .expect("mpesa gateway")
+        };
+
+        // two pages of records, the third page is empty
+        let (base_url, hits) = start_mock_server(get_handler("paged"));
+        let output_details = get_mpesa_gateway(base_url)
+            .pull_transactions_query_all(get_input_details())
+            .await;
+        assert_eq!(output_details.is_complete(), true);
+        let records = &output_details.records;
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].transactionId, "OHR5HNL2P6");
+        assert_eq!(records[0].msisdn, "254722000000");
+        assert_eq!(records[0].amount, "10.0");
+        assert_eq!(records[0].billreference, Some(String::from("INV-001")));
+        assert_eq!(records[1].amount, "250");
+        assert_eq!(records[2].transactionId, "OHR5HNL2P8");
+        assert_eq!(output_details.offset_value, 3);
+        assert_eq!(hits.load(Ordering::SeqCst), 4);
+
+        // the records of the first page are kept when the second page returns an error response
+        let (base_url, hits) = start_mock_server(get_handler("error"));
+        let output_details = get_mpesa_gateway(base_url)
+            .pull_transactions_query_all(get_input_details())
+            .await;
+        assert_eq!(output_details.is_complete(), false);
+        assert_eq!(output_details.records.len(), 2);
+        assert_eq!(output_details.offset_value, 2);
+        assert_eq!(
+            output_details
+                .error_response_data
+                .expect("error response data")
+                .errorCode,
+            Some(String::from("500.003.02"))
+        );
+        assert_eq!(output_details.error, None);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        // the records of the first page are kept when the second page cannot be decoded
+        let (base_url, hits) = start_mock_server(get_handler("decode"));
+        let output_details = get_mpesa_gateway(base_url)
+            .pull_transactions_query_all(get_input_details())
+            .await;
+        assert_eq!(output_details.records.len(), 2);
+        assert_eq!(output_details.offset_value, 2);
+        assert!(matches!(
+            output_details.error,
+            Some(MpesaError::JsonDecode(_))
+        ));
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        // the records of the first page are kept when the second page is rejected after
+        // re-authentication
+        let (base_url, hits) = start_mock_server(get_handler("unauthorized"));
+        let output_details = get_mpesa_gateway(base_url)
+            .pull_transactions_query_all(get_input_details())
+            .await;
+        assert_eq!(output_details.records.len(), 2);
+        assert_eq!(output_details.offset_value, 2);
+        assert_eq!(
+            output_details.error,
+            Some(MpesaError::Auth(String::from(
+                "access token was rejected again after re-authentication"
+            )))
+        );
+        assert_eq!(hits.load(Ordering::SeqCst), 5);
+
+        // a page that cannot be reached is returned as the error
+        let (base_url, hits) = start_mock_server(get_handler("paged"));
+        let output_details = get_mpesa_gateway(base_url.to_string())
+            .pull_transactions_query_all(
+                PullTransactionsQueryInputDetails::new(
+                    Some(String::from("http://127.0.0.1:1/pulltransactions/v1/query")),
+                    String::from("600000"),
+                    String::from("2020-08-04 08:36:00"),
+                    String::from("2020-08-16 10:10:00"),
+                    0,
+                )
+                .expect("pull transactions query details"),
+            )
+            .await;
+        assert_eq!(output_details.records.len(), 0);
+        assert!(matches!(
+            output_details.error,
+            Some(MpesaError::Transport(_))
+        ));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // a repeated page ends the paging
+        let (base_url, hits) = start_mock_server(get_handler("repeat"));
+        let output_details = get_mpesa_gateway(base_url)
+            .pull_transactions_query_all(get_input_details())
+            .await;
+        assert_eq!(output_details.is_complete(), true);
+        assert_eq!(output_details.records.len(), 2);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        // the page limit stops the paging, the records and the offset to resume from are kept
+        let (base_url, hits) = start_mock_server(get_handler("endless"));
+        let output_details = get_mpesa_gateway(base_url)
+            .pull_transactions_query_all(get_input_details())
+            .await;
+        assert_eq!(
+            output_details.records.len(),
+            PULL_TRANSACTIONS_MAX_PAGES as usize
+        );
+        assert_eq!(output_details.offset_value, PULL_TRANSACTIONS_MAX_PAGES);
+        assert!(matches!(output_details.error, Some(MpesaError::Api { .. })));
+        assert_eq!(
+            hits.load(Ordering::SeqCst),
+            PULL_TRANSACTIONS_MAX_PAGES as usize + 1
+        );
+    }
+
+    #[test]
+    fn test_b2c_account_top_up_input_details() {
+        let get_input_details = |party_b: &str, _requester: Option<String>| {
+            B2CAccountTopUpInputDetails::new(
+                Some(String::from(
+                    "https://sandbox.safaricom.co.ke/mpesa/b2b/v1/paymentrequest",
+                )),
+                String::from("testapi"),
+                String::from("***"),
+                239,
+                String::from("600979"),
+                party_b.to_string(),
+                String::from("353353"),
+                _requester,
+                String::from("OK"),
+                String::from("https://mydomain.com/b2b/queue/"),
+                String::from("https://mydomain.com/b2b/result/"),
+            )
+        };
+
+        let _result = get_input_details("600000", None);
+        assert_eq!(_result.is_ok(), true);
+        if let Ok(b2c_account_top_up_details) = _result {
+            assert_eq!(
+                b2c_account_top_up_details.get_command_id(),
+                String::from("BusinessPayToBulk")
+            );
+        }
+
+        let _result = get_input_details("600000", Some(String::from("254708374149")));
+        assert_eq!(_result.is_ok(), true);
+
+        let _result = get_input_details("600000", Some(String::from(" ")));
+        assert_eq!(_result.is_err(), true);
+
+        let _result = get_input_details("254708374149", None);
+        assert_eq!(_result.is_err(), true);
+    }
+
+    #[test]
+    fn test_get_b2_pochi_result_parameters_output_details() {
+        let result_parameters = ResultParameter {
+            ResultParameter: vec![
+                ResultParameterDetails {
+                    Key: String::from("TransactionAmount"),
+                    Value: MixedTypeValue::IntegerValue(10),
+                },
+                ResultParameterDetails {
+                    Key: String::from("TransactionReceipt"),
+                    Value: MixedTypeValue::StringValue(String::from("NLJ41HAY6Q")),
+                },
+                ResultParameterDetails {
+                    Key: String::from("ReceiverPartyPublicName"),
+                    Value: MixedTypeValue::StringValue(String::from("254708374149 - John Doe")),
+                },
+                ResultParameterDetails {
+                    Key: String::from("ReceiverPartyType"),
+                    Value: MixedTypeValue::StringValue(String::from("Pochi")),
+                },
+            ],
+        };
+
+        let consumer_key = String::from("***");
+        let consumer_secret = String::from("***");
+        let mpesa_gateway = MpesaGateway::new(consumer_key, consumer_secret, Environment::Sandbox)
+            .expect("mpesa gateway");
+
+        let b2_pochi_result_parameters_output_details =
+            mpesa_gateway.get_b2_pochi_result_parameters_output_details(&result_parameters);
+
+        assert_eq!(
+            b2_pochi_result_parameters_output_details
+                .B2CResultParameters
+                .TransactionAmount,
+            10.0
+        );
+        assert_eq!(
+            b2_pochi_result_parameters_output_details
+                .B2CResultParameters
+                .TransactionReceipt,
+            String::from("NLJ41HAY6Q")
+        );
+        assert_eq!(
+            b2_pochi_result_parameters_output_details.AdditionalParameters,
+            vec![(String::from("ReceiverPartyType"), String::from("Pochi"))]
+        );
+    }
+
+    #[test]
+    fn test_standing_order_input_details() {
+        let get_input_details = |end_date: &str, receiver_party_identifier_type: &str| {
+            StandingOrderInputDetails::new(
+                Some(String::from(
+                    "https://sandbox.safaricom.co.ke/standingorder/v1/createStandingOrderExternal",
+                )),
+                String::from("Test Standing Order"),
+                String::from("20240905"),
+                end_date.to_string(),
+                String::from("174379"),
+                String::from("Standing Order Customer Pay Bill"),
+                receiver_party_identifier_type.to_string(),
+                4500,
+                254708374149,
+                String::from("https://mydomain.com/standingorder/callback"),
+                String::from("Test"),
+                String::from("Test"),
+                StandingOrderFrequency::Monthly,
+            )
+        };
+
+        let _result = get_input_details("20250905", "4");
+        assert_eq!(_result.is_ok(), true);
+        if let Ok(standing_order_details) = _result {
+            assert_eq!(
+                standing_order_details.get_frequency().get_code(),
+                String::from("4")
+            );
+        }
+
+        // paybill standing orders use receiver party identifier type 4
+        let _result = get_input_details("20250905", "2");
+        assert_eq!(_result.is_err(), true);
+
+        let _result = get_input_details("20240904", "4");
+        assert_eq!(_result.is_err(), true);
+    }
+
+    #[test]
+    fn test_sim_swap_is_swapped_within_days() {
+        let get_response_data = |last_swap_date: Option<String>| SimSwapResponseData {
+            ResponseHeader: SimSwapResponseHeader {
+                responseRefID: Some(String::from("4568-4a1f-96a8-3b2d0ed6d7e5")),
+                responseCode: Some(String::from("200")),
+                responseDesc: Some(String::from("Success")),
+            },
+            ResponseBody: SimSwapResponseBody {
+                customerNumber: Some(String::from("254708374149")),
+                lastSwapDate: last_swap_date,
+            },
+        };
+
+        let now = chrono::NaiveDate::from_ymd_opt(2019, 10, 25)
+            .and_then(|x| x.and_hms_opt(10, 40, 17))
+            .expect("now");
+
+        let response_data = get_response_data(Some(String::from("2019-10-23 10:40:17")));
+        assert_eq!(response_data.is_swapped_within_days_at(7, now), Ok(true));
+        assert_eq!(response_data.is_swapped_within_days_at(1, now), Ok(false));
+
+        // the window ends exactly the given number of days before now
+        assert_eq!(response_data.is_swapped_within_days_at(2, now), Ok(true));
+        let now = now + chrono::Duration::seconds(1);
+        assert_eq!(response_data.is_swapped_within_days_at(2, now), Ok(false));
+
+        let response_data = get_response_data(Some(String::from("2019-10-23")));
+        assert_eq!(response_data.is_swapped_within_days_at(2, now), Ok(false));
+        assert_eq!(response_data.is_swapped_within_days_at(3, now), Ok(true));
+
+        // now defaults to the current Africa/Nairobi time
+        let last_swap_date = (util::util::get_nairobi_time() - chrono::Duration::days(2))
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+        let response_data = get_response_data(Some(last_swap_date));
+        assert_eq!(response_data.is_swapped_within_days(7), Ok(true));
+        assert_eq!(response_data.is_swapped_within_days(1), Ok(false));
+
+        let response_data = get_response_data(None);
+        assert_eq!(response_data.is_swapped_within_days(7), Ok(false));
+
+        let response_data = get_response_data(Some(String::from("23/10/2019")));
+        assert_eq!(response_data.is_swapped_within_days(7).is_err(), true);
+    }
+
+    #[test]
+    fn test_b2c_with_identity_check() {
+        let business_to_customer_details = BusinessToCustomerInputDetails::new(
+            Some(String::from(
+                "https://sandbox.safaricom.co.ke/mpesa/b2c/v3/paymentrequest",
+            )),
+            String::from("feb5e3f2-fbbc-4745-844c-ee37b546f627"),
+            String::from("testapi"),
+            String::from("***"),
+            String::from("SalaryPayment"),
+            150000,
+            600996,
+            String::from("254708374149"),
+            String::from("July salary"),
+            String::from("https://mydomain.com/b2c/queue"),
+            String::from("https://mydomain.com/b2c/result"),
+            String::from(""),
+        )
+        .expect("business to customer details");
+        assert_eq!(
+            business_to_customer_details.get_identity_check().is_none(),
+            true
+        );
+
+        let get_identity_check = |_msisdn: u64| {
+            CustomerIdentityInputDetails::new(
+                String::from("https://mydomain.com/identity/check"),
+                _msisdn,
+                CustomerIdType::NationalId,
+                String::from("12345678"),
+            )
+            .expect("customer identity details")
+        };
+
+        let _result =
+            business_to_customer_details.with_identity_check(get_identity_check(254708374149));
+        assert_eq!(_result.is_ok(), true);
+        if let Ok(business_to_customer_details) = _result {
+            assert_eq!(
+                business_to_customer_details.get_identity_check().is_some(),
+                true
+            );
+
+            // the identity check has to be for the b2c recipient
+            let _result =
+                business_to_customer_details.with_identity_check(get_identity_check(254708374150));
+            assert_eq!(_result.is_err(), true);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_b2c_identity_check_gate() {
+        // the identity check answers with identity_response, b2c requests are counted in payments
+        let get_handler = |identity_response: (u16, &'static str), payments: Arc<AtomicUsize>| {
+            move |request: &str| {
+                if request.starts_with("GET /oauth/v1/generate") {
+                    return (
+                        200,
+                        String::from(r#"{"access_token":"token1","expires_in":"3599"}"#),
+                    );
+                }
+
+                if request.starts_with("POST /identity/check") {
+                    return (identity_response.0, String::from(identity_response.1));
+                }
+
+                payments.fetch_add(1, Ordering::SeqCst);
+                (
+                    200,
+                    String::from(
+                        r#"{"ConversationID":"AG_20191219_00005797af5d7d75f652","OriginatorConversationID":"16740-34861180-1","ResponseCode":"0","ResponseDescription":"Accept the service request successfully."}"#,
+                    ),
+                )
+            }
+        };
+
+        let get_business_to_customer_details = |identity_check_url: String| {
+            BusinessToCustomerInputDetails::new(
+                None,
+                String::from("feb5e3f2-fbbc-4745-844c-ee37b546f627"),
+                String::from("testapi"),
+                String::from("***"),
+                String::from("SalaryPayment"),
+                150000,
+                600996,
+                String::from("254708374149"),
+                String::from("July salary"),
+                String::from("https://mydomain.com/b2c/queue"),
+                String::from("https://mydomain.com/b2c/result"),
+                String::from(""),
+            )
+            .expect("business to customer details")
+            .with_identity_check(
+                CustomerIdentityInputDetails::new(
+                    identity_check_url,
+                    254708374149,
+                    CustomerIdType::NationalId,
+                    String::from("12345678"),
+                )
+                .expect("customer identity details"),
+            )
+            .expect("identity check")
+        };
+
+        let send_b2c = |identity_response: (u16, &'static str)| {
+            let payments = Arc::new(AtomicUsize::new(0));
+            let (base_url, _hits) =
+                start_mock_server(get_handler(identity_response, Arc::clone(&payments)));
+
+            async move {
+                let mpesa_gateway = MpesaGateway::new(
+                    String::from("***"),
+                    String::from("***"),
+                    Environment::Custom(base_url.to_string()),
+                )
+                .expect("mpesa gateway");
+
+                let _result = mpesa_gateway
+                    .b2c(get_business_to_customer_details(format!(
+                        "{}/identity/check",
+                        base_url
+                    )))
+                    .await;
+
+                (_result, payments.load(Ordering::SeqCst))
+            }
+        };
+
+        // the identity matches, the payment is sent
+        let (_result, payments) = send_b2c((
+            200,
+            r#"{"OriginatorConversationID":"5118-111210482-1","ResponseCode":"0","ResponseDescription":"Success","IsMatch":true}"#,
+        ))
+        .await;
+        let (business_to_customer_response_data, _) = _result.expect("b2c");
+        assert_eq!(
+            business_to_customer_response_data
+                .expect("b2c response data")
+                .ResponseCode,
+            Some(String::from("0"))
+        );
+        assert_eq!(payments, 1);
+
+        // the identity does not match, the payment is not sent
+        let (_result, payments) = send_b2c((
+            200,
+            r#"{"OriginatorConversationID":"5118-111210482-1","ResponseCode":"0","ResponseDescription":"Success","IsMatch":false}"#,
+        ))
+        .await;
+        assert_eq!(
+            _result.err(),
+            Some(MpesaError::validation(
+                "customer identity",
+                "does not match"
+            ))
+        );
+        assert_eq!(payments, 0);
+
+        // the identity check returns an error response, the payment is not sent
+        let (_result, payments) = send_b2c((
+            400,
+            r#"{"requestId":"11728-2929992-1","errorCode":"400.002.02","errorMessage":"Bad Request - Invalid IDNumber"}"#,
+        ))
+        .await;
+        assert_eq!(
+            _result.err(),
+            Some(MpesaError::Api {
+                request_id: Some(String::from("11728-2929992-1")),
+                error_code: Some(String::from("400.002.02")),
+                error_message: Some(String::from("Bad Request - Invalid IDNumber")),
+            })
+        );
+        assert_eq!(payments, 0);
+
+        // the identity check cannot be reached, the payment is not sent
+        let identity_check_url = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("closed port");
+            format!(
+                "http://{}/identity/check",
+                listener.local_addr().expect("local addr")
+            )
+        };
+        let payments = Arc::new(AtomicUsize::new(0));
+        let (base_url, _hits) = start_mock_server(get_handler(
+            (200, r#"{"ResponseCode":"0","IsMatch":true}"#),
+            Arc::clone(&payments),
+        ));
+        let mpesa_gateway = MpesaGateway::new(
+            String::from("***"),
+            String::from("***"),
+            Environment::Custom(base_url),
+        )
+        .expect("mpesa gateway");
+        let _result = mpesa_gateway
+            .b2c(get_business_to_customer_details(identity_check_url))
+            .await;
+        assert!(matches!(_result, Err(MpesaError::Transport(_))));
+        assert_eq!(payments.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_c2b_msisdn_matches_phone_number() {
+        let _msisdn = C2bMsisdn::from(format!("{:x}", Sha256::digest(b"254708374149")));
+        assert_eq!(_msisdn.is_hashed(), true);
+        assert_eq!(_msisdn.matches_phone_number("0708374149"), true);
+        assert_eq!(_msisdn.matches_phone_number("+254708374149"), true);
+        assert_eq!(_msisdn.matches_phone_number("0708374150"), false);
+
+        let _msisdn = C2bMsisdn::from(String::from("254708374149"));
+        assert_eq!(_msisdn, C2bMsisdn::Plain(String::from("254708374149")));
+        assert_eq!(_msisdn.matches_phone_number("708374149"), true);
+
+        let _msisdn = C2bMsisdn::from(String::from("2547 ***** 149"));
+        assert_eq!(_msisdn, C2bMsisdn::Masked(String::from("2547 ***** 149")));
+        assert_eq!(_msisdn.matches_phone_number("0708374149"), true);
+        assert_eq!(_msisdn.matches_phone_number("0708374150"), false);
+    }
+
+    #[test]
+    fn test_business_transfer_input_details() {
+        let get_input_details = |command_id: BusinessTransferCommand,
+                                 sender_identifier_type: &str,
+                                 reciever_identifier_type: &str| {
+            BusinessTransferInputDetails::new(
+                Some(String::from(
+                    "https://sandbox.safaricom.co.ke/mpesa/b2b/v1/paymentrequest",
+                )),
+                String::from("testapi"),
+                String::from("***"),
+                command_id,
+                sender_identifier_type.to_string(),
+                reciever_identifier_type.to_string(),
+                239,
+                String::from("600979"),
+                String::from("600000"),
+                String::from("353353"),
+                None,
+                String::from("OK"),
+                String::from("https://mydomain.com/b2b/queue/"),
+                String::from("https://mydomain.com/b2b/result/"),
+            )
+        };
+
+        let _result = get_input_details(
+            BusinessTransferCommand::BusinessToBusinessTransfer,
+            "4",
+            "4",
+        );
+        assert_eq!(_result.is_ok(), true);
+        if let Ok(business_transfer_details) = _result {
+            assert_eq!(
+                business_transfer_details.get_command_id(),
+                String::from("BusinessToBusinessTransfer")
+            );
+        }
+
+        let _result = get_input_details(
+            BusinessTransferCommand::MerchantToMerchantTransfer,
+            "2",
+            "2",
+        );
+        assert_eq!(_result.is_ok(), true);
+
+        // merchant to merchant transfers are between till numbers
+        let _result = get_input_details(
+            BusinessTransferCommand::MerchantToMerchantTransfer,
+            "4",
+            "4",
+        );
+        assert_eq!(_result.is_err(), true);
+
+        let _result = get_input_details(
+            BusinessTransferCommand::MerchantTransferFromMerchantToWorking,
+            "2",
+            "2",
+        );
+        assert_eq!(_result.is_err(), true);
+    }
+
+    #[test]
+    fn test_generate_security_credential() {
+        use rsa::{pkcs8::DecodePrivateKey, Pkcs1v15Encrypt, RsaPrivateKey};
+        use security_credential::security_credential::generate_security_credential;
+
+        let certificate_pem = include_str!("security_credential/test_data/test_certificate.pem");
+        let certificate_der = include_bytes!("security_credential/test_data/test_certificate.cer");
+        let private_key = RsaPrivateKey::from_pkcs8_pem(include_str!(
+            "security_credential/test_data/test_private_key.pem"
+        ))
+        .expect("private key");
+
+        for certificate in [certificate_pem.as_bytes(), &certificate_der[..]] {
+            let security_credential = generate_security_credential("Safaricom999!*!", certificate)
+                .expect("security credential");
+
+            let encrypted_password = general_purpose::STANDARD
+                .decode(security_credential)
+                .expect("base64");
+            let initiator_password = private_key
+                .decrypt(Pkcs1v15Encrypt, &encrypted_password)
+                .expect("decrypt");
+            assert_eq!(initiator_password, b"Safaricom999!*!".to_vec());
+        }
+
+        // the credential can be passed straight to the input constructors
+        let _result = BusinessToCustomerInputDetails::new(
+            Some(String::from(
+                "https://sandbox.safaricom.co.ke/mpesa/b2c/v3/paymentrequest",
+            )),
+            String::from("feb5e3f2-fbbc-4745-844c-ee37b546f627"),
+            String::from("testapi"),
+            generate_security_credential("Safaricom999!*!", certificate_pem.as_bytes())
+                .expect("security credential"),
+            String::from("BusinessPayment"),
+            10,
+            600996,
+            String::from("254708374149"),
+            String::from("Test remarks"),
+            String::from("https://mydomain.com/b2c/queue"),
+            String::from("https://mydomain.com/b2c/result"),
+            String::from(""),
+        );
+        assert_eq!(_result.is_ok(), true);
+
+        assert_eq!(
+            generate_security_credential("Safaricom999!*!", b"not a certificate").is_err(),
+            true
+        );
+        assert_eq!(
+            generate_security_credential("", certificate_pem.as_bytes()).is_err(),
+            true
+        );
+    }
+
+    #[test]
+    fn test_security_credential_certificate_selection() {
+        use security_credential::security_credential::{
+            generate_security_credential_for_environment, get_certificate, PRODUCTION_CERTIFICATE,
+            SANDBOX_CERTIFICATE,
+        };
+
+        let certificate = get_certificate(&Environment::Sandbox).expect("sandbox certificate");
+        assert_eq!(certificate.as_ptr(), SANDBOX_CERTIFICATE.as_ptr());
+        assert_eq!(certificate, SANDBOX_CERTIFICATE);
+
+        let certificate =
+            get_certificate(&Environment::Production).expect("production certificate");
+        assert_eq!(certificate.as_ptr(), PRODUCTION_CERTIFICATE.as_ptr());
+        assert_eq!(certificate, PRODUCTION_CERTIFICATE);
+
+        let environment = Environment::Custom(String::from("http://localhost:8080"));
+        assert_eq!(
+            get_certificate(&environment).err(),
+            Some(MpesaError::validation(
+                "certificate",
+                "is not bundled for a custom environment",
+            ))
+        );
+        assert_eq!(
+            generate_security_credential_for_environment("Safaricom999!*!", &environment).is_err(),
+            true
+        );
+
+        let _result = BusinessToCustomerInputDetails::new_with_initiator_password(
+            None,
+            &environment,
+            String::from("feb5e3f2-fbbc-4745-844c-ee37b546f627"),
+            String::from("testapi"),
+            String::from("Safaricom999!*!"),
+            String::from("BusinessPayment"),
+            10,
+            600996,
+            String::from("254708374149"),
+            String::from("Test remarks"),
+            String::from("https://mydomain.com/b2c/queue"),
+            String::from("https://mydomain.com/b2c/result"),
+            String::from(""),
+        );
+        assert_eq!(_result.is_err(), true);
+
+        let _result = BusinessPayBillInputDetails::new_with_initiator_password(
+            None,
+            &Environment::Sandbox,
+            String::from("testapi"),
+            String::from(""),
+            String::from("BusinessPayBill"),
+            String::from("4"),
+            String::from("4"),
+            10,
+            String::from("600992"),
+            String::from("000000"),
+            String::from("353353"),
+            String::from("254708374149"),
+            String::from("ok"),
+            String::from("https://mydomain.com/b2b/queue"),
+            String::from("https://mydomain.com/b2b/result"),
+        );
+        assert_eq!(
+            _result.err(),
+            Some(MpesaError::validation("initiator password", "is empty"))
+        );
+    }
+
+    #[test]
+    #[ignore = "needs SandboxCertificate.cer and ProductionCertificate.cer from the daraja portal in src/security_credential/certificates"]
+    fn test_security_credential_bundled_certificates() {
+        use security_credential::security_credential::generate_security_credential_for_environment;
+
+        for environment in [Environment::Sandbox, Environment::Production] {
+            let security_credential =
+                generate_security_credential_for_environment("Safaricom999!*!", &environment)
+                    .expect("security credential");
+
+            // the M-Pesa certificates carry 2048 bit RSA keys, the ciphertext is as long as the modulus
+            assert_eq!(security_credential.len(), 344);
+            let encrypted_password = general_purpose::STANDARD
+                .decode(&security_credential)
+                .expect("base64");
+            assert_eq!(encrypted_password.len(), 256);
+
+            // PKCS#1 v1.5 padding is random, every call gives a different credential
+            assert_ne!(
+                generate_security_credential_for_environment("Safaricom999!*!", &environment),
+                Ok(security_credential)
+            );
+        }
+    }
+
+    #[test]
+    fn test_c2b_payment_input_details_with_pass_key() {
+        let pass_key =
+            String::from("bfb279f9aa9bdbcf158e97dd71a467cd2e0c893059b10f78e6b72ada1ed2c919");
+
+        let _result = CustomerToBusinessPaymentInputDetails::new_with_pass_key(
+            Some(String::from(
+                "https://sandbox.safaricom.co.ke/mpesa/stkpush/v1/processrequest",
+            )),
+            String::from("174379"),
+            pass_key.to_string(),
+            String::from("CustomerPayBillOnline"),
+            1,
+            254708374149,
+            174379,
+            254708374149,
+            String::from("https://mydomain.com/path"),
+            String::from("Company X"),
+            String::from("Payment of X"),
+        );
+        assert_eq!(_result.is_ok(), true);
+
+        // the password has to be generated from the same time stamp
+        let time_stamp = String::from("20230915143020");
+        let get_input_details = |_password: String| {
+            CustomerToBusinessPaymentInputDetails::new(
+                Some(String::from(
+                    "https://sandbox.safaricom.co.ke/mpesa/stkpush/v1/processrequest",
+                )),
+                String::from("174379"),
+                _password,
+                time_stamp.to_string(),
+                String::from("CustomerPayBillOnline"),
+                1,
+                254708374149,
+                174379,
+                254708374149,
+                String::from("https://mydomain.com/path"),
+                String::from("Company X"),
+                String::from("Payment of X"),
+            )
+        };
+
+        let _result = get_input_details(util::util::build_stk_password(
+            "174379",
+            &pass_key,
+            &time_stamp,
+        ));
+        assert_eq!(_result.is_ok(), true);
+
+        let _result = get_input_details(util::util::build_stk_password(
+            "174379",
+            &pass_key,
+            "20230915143021",
+        ));
+        assert_eq!(_result.is_err(), true);
     }
 }
@@ -1,5 +1,13 @@
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::environment::environment::{Endpoint, Environment};
+use crate::error::error::MpesaError;
+use crate::security_credential::security_credential::generate_security_credential_for_environment;
+use crate::util::util::{
+    build_stk_password, get_nairobi_time, get_stk_time_stamp, is_valid_stk_password,
+};
 
 #[derive(Serialize)]
 pub struct ValidationResponseData {
@@ -13,6 +21,12 @@ pub struct ConfirmationResponseData {
     pub ResultDesc: String,
 }
 
+#[derive(Serialize)]
+pub struct BillManagerPaymentNotificationResponseData {
+    pub rescode: String,
+    pub resmsg: String,
+}
+
 #[derive(Serialize, Debug)]
 pub struct RegisterUrlData {
     pub ShortCode: String,
@@ -51,6 +65,23 @@ pub struct CustomerToBusinessPaymentData {
     pub TransactionDesc: String,
 }
 
+#[derive(Serialize, Debug)]
+pub struct CustomerToBusinessPaymentQueryData {
+    pub BusinessShortCode: String,
+    pub Password: String,
+    pub Timestamp: String,
+    pub CheckoutRequestID: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CustomerToBusinessSimulateData {
+    pub ShortCode: String,
+    pub CommandID: String,
+    pub Amount: u32,
+    pub Msisdn: u64,
+    pub BillRefNumber: String,
+}
+
 #[derive(Serialize, Debug)]
 pub struct BusinessPayBillData {
     pub Initiator: String,
@@ -85,6 +116,329 @@ pub struct BusinessBuyGoodsData {
     pub ResultURL: String,
 }
 
+#[derive(Serialize, Debug)]
+pub struct TransactionStatusData {
+    pub Initiator: String,
+    pub SecurityCredential: String,
+    pub CommandID: String,
+    pub TransactionID: String,
+    pub OriginalConversationID: String,
+    pub PartyA: String,
+    pub IdentifierType: String,
+    pub ResultURL: String,
+    pub QueueTimeOutURL: String,
+    pub Remarks: String,
+    pub Occasion: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AccountBalanceData {
+    pub Initiator: String,
+    pub SecurityCredential: String,
+    pub CommandID: String,
+    pub PartyA: String,
+    pub IdentifierType: String,
+    pub Remarks: String,
+    pub QueueTimeOutURL: String,
+    pub ResultURL: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ReversalData {
+    pub Initiator: String,
+    pub SecurityCredential: String,
+    pub CommandID: String,
+    pub TransactionID: String,
+    pub Amount: u32,
+    pub ReceiverParty: String,
+    pub RecieverIdentifierType: String,
+    pub ResultURL: String,
+    pub QueueTimeOutURL: String,
+    pub Remarks: String,
+    pub Occasion: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BusinessExpressCheckoutData {
+    pub primaryShortCode: String,
+    pub receiverShortCode: String,
+    pub amount: String,
+    pub paymentRef: String,
+    pub callbackUrl: String,
+    pub partnerName: String,
+    pub RequestRefID: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DynamicQrData {
+    pub MerchantName: String,
+    pub RefNo: String,
+    pub Amount: u32,
+    pub TrxCode: String,
+    pub CPI: String,
+    pub Size: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RemitTaxData {
+    pub Initiator: String,
+    pub SecurityCredential: String,
+    pub CommandID: String,
+    pub SenderIdentifierType: String,
+    pub RecieverIdentifierType: String,
+    pub Amount: u32,
+    pub PartyA: String,
+    pub PartyB: String,
+    pub AccountReference: String,
+    pub Remarks: String,
+    pub QueueTimeOutURL: String,
+    pub ResultURL: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BillManagerOptInData {
+    pub shortcode: String,
+    pub email: String,
+    pub officialContact: String,
+    pub sendReminders: String,
+    pub logo: String,
+    pub callbackurl: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BillManagerInvoiceItemData {
+    pub itemName: String,
+    pub amount: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BillManagerInvoiceData {
+    pub externalReference: String,
+    pub billedFullName: String,
+    pub billedPhoneNumber: String,
+    pub billedPeriod: String,
+    pub invoiceName: String,
+    pub dueDate: String,
+    pub accountReference: String,
+    pub amount: String,
+    pub invoiceItems: Vec<BillManagerInvoiceItemData>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BillManagerCancelInvoiceData {
+    pub externalReference: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BillManagerReconciliationData {
+    pub paymentDate: String,
+    pub paidAmount: String,
+    pub accountReference: String,
+    pub transactionId: String,
+    pub phoneNumber: String,
+    pub fullName: String,
+    pub invoiceName: String,
+    pub externalReference: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PullTransactionsRegisterData {
+    pub ShortCode: String,
+    pub RequestType: String,
+    pub NominatedNumber: String,
+    pub CallBackURL: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PullTransactionsQueryData {
+    pub ShortCode: String,
+    pub StartDate: String,
+    pub EndDate: String,
+    pub OffSetValue: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct B2CAccountTopUpData {
+    pub Initiator: String,
+    pub SecurityCredential: String,
+    pub CommandID: String,
+    pub SenderIdentifierType: String,
+    pub RecieverIdentifierType: String,
+    pub Amount: u32,
+    pub PartyA: String,
+    pub PartyB: String,
+    pub AccountReference: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub Requester: Option<String>,
+    pub Remarks: String,
+    pub QueueTimeOutURL: String,
+    pub ResultURL: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct B2PochiData {
+    pub OriginatorConversationID: String,
+    pub InitiatorName: String,
+    pub SecurityCredential: String,
+    pub CommandID: String,
+    pub Amount: u32,
+    pub PartyA: u32,
+    pub PartyB: String,
+    pub Remarks: String,
+    pub QueueTimeOutURL: String,
+    pub ResultURL: String,
+    pub Occasion: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct StandingOrderData {
+    pub StandingOrderName: String,
+    pub StartDate: String,
+    pub EndDate: String,
+    pub BusinessShortCode: String,
+    pub TransactionType: String,
+    pub ReceiverPartyIdentifierType: String,
+    pub Amount: String,
+    pub PartyA: String,
+    pub CallBackURL: String,
+    pub AccountReference: String,
+    pub TransactionDesc: String,
+    pub Frequency: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SimSwapData {
+    pub customerNumber: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CustomerIdentityData {
+    pub MSISDN: String,
+    pub IDType: String,
+    pub IDNumber: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BusinessTransferData {
+    pub Initiator: String,
+    pub SecurityCredential: String,
+    pub CommandID: String,
+    pub SenderIdentifierType: String,
+    pub RecieverIdentifierType: String,
+    pub Amount: u32,
+    pub PartyA: String,
+    pub PartyB: String,
+    pub AccountReference: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub Requester: Option<String>,
+    pub Remarks: String,
+    pub QueueTimeOutURL: String,
+    pub ResultURL: String,
+}
+
+// The c2b v2 endpoints send the MSISDN either in plain, masked (e.g. 2547 ***** 149)
+// or SHA-256 hashed form
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(from = "String")]
+pub enum C2bMsisdn {
+    Plain(String),
+    Masked(String),
+    Hashed(String),
+}
+
+impl From<String> for C2bMsisdn {
+    fn from(_msisdn: String) -> Self {
+        let _msisdn = _msisdn.trim().to_string();
+        let is_digits = |x: &str| !x.is_empty() && x.chars().all(|c| c.is_ascii_digit());
+
+        if _msisdn.len() == 64 && _msisdn.chars().all(|c| c.is_ascii_hexdigit()) {
+            C2bMsisdn::Hashed(_msisdn)
+        } else if is_digits(_msisdn.trim_start_matches('+')) {
+            C2bMsisdn::Plain(_msisdn)
+        } else {
+            // Anything else (e.g. 2547 ***** 149) does not carry the full number
+            C2bMsisdn::Masked(_msisdn)
+        }
+    }
+}
+
+impl std::fmt::Display for C2bMsisdn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            C2bMsisdn::Plain(x) | C2bMsisdn::Masked(x) | C2bMsisdn::Hashed(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+impl C2bMsisdn {
+    pub fn is_hashed(&self) -> bool {
+        matches!(self, C2bMsisdn::Hashed(_))
+    }
+
+    // Checks whether this MSISDN belongs to the given phone number
+    // e.g. 254708374149, +254708374149, 0708374149 or 708374149.
+    // A hashed MSISDN is compared with the SHA-256 hash of the phone number (2547XXXXXXXX),
+    // a masked MSISDN is compared on the digits that are not masked.
+    pub fn matches_phone_number(&self, phone_number: &str) -> bool {
+        let phone_number = match normalize_phone_number(phone_number) {
+            Some(x) => x,
+            None => return false,
+        };
+
+        match self {
+            C2bMsisdn::Plain(x) => match normalize_phone_number(x) {
+                Some(x) => x.eq(&phone_number),
+                None => false,
+            },
+            C2bMsisdn::Hashed(x) => x.eq_ignore_ascii_case(&hash_phone_number(&phone_number)),
+            C2bMsisdn::Masked(x) => {
+                let masked_msisdn = x.replace(" ", "");
+                let mut masked_digits = masked_msisdn.chars().filter(|c| *c != '*');
+
+                // the masked form has to start with the country code and end with the
+                // same digits as the phone number
+                if !masked_msisdn.starts_with("254")
+                    || masked_msisdn.len() != phone_number.len()
+                    || masked_digits.any(|c| !c.is_ascii_digit())
+                {
+                    return false;
+                }
+
+                masked_msisdn
+                    .chars()
+                    .zip(phone_number.chars())
+                    .all(|(a, b)| a == '*' || a == b)
+            }
+        }
+    }
+}
+
+// Returns the phone number in the format 2547XXXXXXXX
+fn normalize_phone_number(phone_number: &str) -> Option<String> {
+    let phone_number = phone_number.replace(" ", "");
+    let phone_number = phone_number.trim_start_matches('+');
+
+    if phone_number.is_empty() || !phone_number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let phone_number = if phone_number.len() == 12 && phone_number.starts_with("254") {
+        phone_number.to_string()
+    } else if phone_number.len() == 10 && phone_number.starts_with('0') {
+        format!("254{}", &phone_number[1..])
+    } else if phone_number.len() == 9 {
+        format!("254{}", phone_number)
+    } else {
+        return None;
+    };
+
+    Some(phone_number)
+}
+
+fn hash_phone_number(phone_number: &str) -> String {
+    format!("{:x}", Sha256::digest(phone_number.as_bytes()))
+}
+
 #[derive(Deserialize)]
 pub struct C2bData {
     pub TransactionType: String,
@@ -96,10 +450,106 @@ pub struct C2bData {
     pub InvoiceNumber: Option<String>,
     pub OrgAccountBalance: String,
     pub ThirdPartyTransID: String,
-    pub MSISDN: String,
-    pub FirstName: String,
-    pub MiddleName: String,
-    pub LastName: String,
+    pub MSISDN: C2bMsisdn,
+    pub FirstName: Option<String>,
+    pub MiddleName: Option<String>,
+    pub LastName: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BillManagerPaymentNotificationData {
+    pub transactionId: String,
+    pub paidAmount: String,
+    pub msisdn: String,
+    pub dateCreated: String,
+    pub accountReference: String,
+    pub shortCode: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PullTransactionsRegisterResponseData {
+    pub ResponseRefID: Option<String>,
+    pub ResponseStatus: Option<String>,
+    pub ShortCode: Option<String>,
+    pub ResponseDescription: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PullTransactionsErrorResponseData {
+    pub requestId: Option<String>,
+    pub errorCode: Option<String>,
+    pub errorMessage: Option<String>,
+}
+
+// msisdn and amount are sent either as json strings or as json numbers
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumberValue {
+    StringValue(String),
+    IntegerValue(u64),
+    FloatValue(f64),
+}
+
+fn deserialize_string_or_number<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = match StringOrNumberValue::deserialize(deserializer)? {
+        StringOrNumberValue::StringValue(x) => x,
+        StringOrNumberValue::IntegerValue(x) => x.to_string(),
+        StringOrNumberValue::FloatValue(x) => x.to_string(),
+    };
+
+    Ok(value)
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PullTransactionsRecord {
+    pub transactionId: String,
+    pub trxDate: String,
+    #[serde(deserialize_with = "deserialize_string_or_number")]
+    pub msisdn: String,
+    pub sender: Option<String>,
+    pub transactiontype: String,
+    pub billreference: Option<String>,
+    #[serde(deserialize_with = "deserialize_string_or_number")]
+    pub amount: String,
+    pub organizationname: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PullTransactionsQueryResponseData {
+    pub ResponseRefID: Option<String>,
+    pub ResponseCode: Option<String>,
+    pub ResponseMessage: Option<String>,
+    pub Response: Option<Vec<Vec<PullTransactionsRecord>>>,
+}
+
+impl PullTransactionsQueryResponseData {
+    // The records are returned in nested batches; flatten them into a single list
+    pub fn get_records(&self) -> Vec<PullTransactionsRecord> {
+        match &self.Response {
+            Some(response) => response.iter().flatten().cloned().collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+// The outcome of pull_transactions_query_all. The records received are always returned, also
+// when a page fails; offset_value is the OffSetValue to resume the query from.
+#[derive(Debug)]
+pub struct PullTransactionsQueryAllOutputDetails {
+    pub records: Vec<PullTransactionsRecord>,
+    pub offset_value: u32,
+    pub error_response_data: Option<PullTransactionsErrorResponseData>,
+    pub error: Option<MpesaError>,
+}
+
+impl PullTransactionsQueryAllOutputDetails {
+    // All pages were received, no error response and no error stopped the paging
+    pub fn is_complete(&self) -> bool {
+        self.error_response_data.is_none() && self.error.is_none()
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -112,9 +562,17 @@ pub struct AuthTokenResponseData {
 pub struct RegisterUrlResponseData {
     pub OriginatorCoversationID: Option<String>,
     pub ConversationID: Option<String>,
+    pub ResponseCode: Option<String>,
     pub ResponseDescription: Option<String>,
 }
 
+#[derive(Deserialize, Debug)]
+pub struct RegisterUrlErrorResponseData {
+    pub requestId: Option<String>,
+    pub errorCode: Option<String>,
+    pub errorMessage: Option<String>,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct BusinessToCustomerResponseData {
     pub OriginatorConversationID: Option<String>,
@@ -209,6 +667,37 @@ pub struct CustomerToBusinessPaymentErrorResponseData {
     pub errorMessage: Option<String>,
 }
 
+#[derive(Deserialize, Debug)]
+pub struct CustomerToBusinessPaymentQueryResponseData {
+    pub ResponseCode: Option<String>,
+    pub ResponseDescription: Option<String>,
+    pub MerchantRequestID: Option<String>,
+    pub CheckoutRequestID: Option<String>,
+    pub ResultCode: Option<String>,
+    pub ResultDesc: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CustomerToBusinessPaymentQueryErrorResponseData {
+    pub requestId: Option<String>,
+    pub errorCode: Option<String>,
+    pub errorMessage: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CustomerToBusinessSimulateResponseData {
+    pub OriginatorCoversationID: Option<String>,
+    pub ResponseCode: Option<String>,
+    pub ResponseDescription: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CustomerToBusinessSimulateErrorResponseData {
+    pub requestId: Option<String>,
+    pub errorCode: Option<String>,
+    pub errorMessage: Option<String>,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct ItemDetails {
     pub Name: String,
@@ -374,65 +863,3601 @@ pub struct BusinessBuyGoodsFailedData {
     pub Result: BusinessBuyGoodsFailedDetails,
 }
 
-// This struct holds  Register Url processing data
-/*
-pub struct RegisterUrlInputDetails {
-    //pub access_token: String,
-    //pub api_url: String,
+// TransactionStatus
+
+#[derive(Deserialize, Debug)]
+pub struct TransactionStatusResponseData {
+    pub OriginatorConversationID: Option<String>,
+    pub ConversationID: Option<String>,
+    pub ResponseCode: Option<String>,
+    pub ResponseDescription: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TransactionStatusErrorResponseData {
+    pub requestId: Option<String>,
+    pub errorCode: Option<String>,
+    pub errorMessage: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TransactionStatusReferenceItemDetails {
+    pub Key: String,
+    pub Value: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TransactionStatusReferenceItem {
+    pub ReferenceItem: TransactionStatusReferenceItemDetails,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TransactionStatusResultDetails {
+    pub ResultType: u8,
+    pub ResultCode: u32,
+    pub ResultDesc: String,
+    pub OriginatorConversationID: String,
+    pub ConversationID: String,
+    pub TransactionID: String,
+    pub ResultParameters: ResultParameter,
+    pub ReferenceData: TransactionStatusReferenceItem,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TransactionStatusResultData {
+    pub Result: TransactionStatusResultDetails,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TransactionStatusFailedDetails {
+    pub ResultType: u8,
+    pub ResultCode: u32,
+    pub ResultDesc: String,
+    pub OriginatorConversationID: String,
+    pub ConversationID: String,
+    pub TransactionID: String,
+    pub ReferenceData: TransactionStatusReferenceItem,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TransactionStatusFailedData {
+    pub Result: TransactionStatusFailedDetails,
+}
+
+// AccountBalance
+
+#[derive(Deserialize, Debug)]
+pub struct AccountBalanceResponseData {
+    pub OriginatorConversationID: Option<String>,
+    pub ConversationID: Option<String>,
+    pub ResponseCode: Option<String>,
+    pub ResponseDescription: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AccountBalanceErrorResponseData {
+    pub requestId: Option<String>,
+    pub errorCode: Option<String>,
+    pub errorMessage: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AccountBalanceResultDetails {
+    pub ResultType: u8,
+    pub ResultCode: u32,
+    pub ResultDesc: String,
+    pub OriginatorConversationID: String,
+    pub ConversationID: String,
+    pub TransactionID: String,
+    pub ResultParameters: ResultParameter,
+    pub ReferenceData: ReferenceItem,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AccountBalanceResultData {
+    pub Result: AccountBalanceResultDetails,
+}
+
+// Reversal
+
+#[derive(Deserialize, Debug)]
+pub struct ReversalResponseData {
+    pub OriginatorConversationID: Option<String>,
+    pub ConversationID: Option<String>,
+    pub ResponseCode: Option<String>,
+    pub ResponseDescription: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReversalErrorResponseData {
+    pub requestId: Option<String>,
+    pub errorCode: Option<String>,
+    pub errorMessage: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReversalResultDetails {
+    pub ResultType: u8,
+    pub ResultCode: u32,
+    pub ResultDesc: String,
+    pub OriginatorConversationID: String,
+    pub ConversationID: String,
+    pub TransactionID: String,
+    pub ResultParameters: ResultParameter,
+    pub ReferenceData: ReferenceItem,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReversalResultData {
+    pub Result: ReversalResultDetails,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReversalFailedDetails {
+    pub ResultType: u8,
+    pub ResultCode: u32,
+    pub ResultDesc: String,
+    pub OriginatorConversationID: String,
+    pub ConversationID: String,
+    pub TransactionID: String,
+    pub ReferenceData: ReferenceItem,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReversalFailedData {
+    pub Result: ReversalFailedDetails,
+}
+
+// BusinessExpressCheckout
+
+#[derive(Deserialize, Debug)]
+pub struct BusinessExpressCheckoutResponseData {
+    pub code: Option<String>,
+    pub status: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BusinessExpressCheckoutErrorResponseData {
+    pub requestId: Option<String>,
+    pub errorCode: Option<String>,
+    pub errorMessage: Option<String>,
+}
+
+// Successful and cancelled/failed callbacks carry different fields
+#[derive(Deserialize, Debug)]
+pub struct BusinessExpressCheckoutResultData {
+    pub resultCode: String,
+    pub resultDesc: String,
+    pub requestId: Option<String>,
+    pub amount: Option<String>,
+    pub resultType: Option<String>,
+    pub conversationID: Option<String>,
+    pub transactionId: Option<String>,
+    pub status: Option<String>,
+    pub paymentReference: Option<String>,
+}
+
+// DynamicQr
+
+#[derive(Deserialize, Debug)]
+pub struct DynamicQrResponseData {
+    pub ResponseCode: Option<String>,
+    pub RequestID: Option<String>,
+    pub ResponseDescription: Option<String>,
+    pub QRCode: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DynamicQrErrorResponseData {
+    pub requestId: Option<String>,
+    pub errorCode: Option<String>,
+    pub errorMessage: Option<String>,
+}
+
+// RemitTax
+// The result and timeout callbacks use BusinessPayBillResultData and BusinessPayBillFailedData
+
+#[derive(Deserialize, Debug)]
+pub struct RemitTaxResponseData {
+    pub OriginatorConversationID: Option<String>,
+    pub ConversationID: Option<String>,
+    pub ResponseCode: Option<String>,
+    pub ResponseDescription: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RemitTaxErrorResponseData {
+    pub requestId: Option<String>,
+    pub errorCode: Option<String>,
+    pub errorMessage: Option<String>,
+}
+
+// B2CAccountTopUp
+// The result and timeout callbacks use BusinessPayBillResultData and BusinessPayBillFailedData
+
+#[derive(Deserialize, Debug)]
+pub struct B2CAccountTopUpResponseData {
+    pub OriginatorConversationID: Option<String>,
+    pub ConversationID: Option<String>,
+    pub ResponseCode: Option<String>,
+    pub ResponseDescription: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct B2CAccountTopUpErrorResponseData {
+    pub requestId: Option<String>,
+    pub errorCode: Option<String>,
+    pub errorMessage: Option<String>,
+}
+
+// B2Pochi
+// The result and timeout callbacks use B2CResultData and B2CFailedData
+
+#[derive(Deserialize, Debug)]
+pub struct B2PochiResponseData {
+    pub ConversationID: Option<String>,
+    pub OriginatorConversationID: Option<String>,
+    pub ResponseCode: Option<String>,
+    pub ResponseDescription: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct B2PochiErrorResponseData {
+    pub requestId: Option<String>,
+    pub errorCode: Option<String>,
+    pub errorMessage: Option<String>,
+}
+
+// StandingOrder (Lipa na M-Pesa Ratiba)
+
+#[derive(Deserialize, Debug)]
+pub struct StandingOrderResponseHeader {
+    pub responseRefID: Option<String>,
+    pub responseCode: Option<String>,
+    pub responseDescription: Option<String>,
+    pub ResultDesc: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StandingOrderResponseBody {
+    pub responseDescription: Option<String>,
+    pub responseCode: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StandingOrderResponseData {
+    pub ResponseHeader: StandingOrderResponseHeader,
+    pub ResponseBody: StandingOrderResponseBody,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StandingOrderErrorResponseData {
+    pub requestId: Option<String>,
+    pub errorCode: Option<String>,
+    pub errorMessage: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StandingOrderCallbackResponseHeader {
+    pub responseRefID: Option<String>,
+    pub requestRefID: Option<String>,
+    #[serde(deserialize_with = "deserialize_string_or_number")]
+    pub responseCode: String,
+    pub responseDescription: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StandingOrderCallbackResponseItem {
+    pub name: String,
+    #[serde(deserialize_with = "deserialize_string_or_number")]
+    pub value: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StandingOrderCallbackResponseBody {
+    pub responseData: Vec<StandingOrderCallbackResponseItem>,
+}
+
+// This is sent to the CallBackURL each time the standing order is executed
+#[derive(Deserialize, Debug)]
+pub struct StandingOrderCallbackData {
+    pub ResponseHeader: StandingOrderCallbackResponseHeader,
+    pub ResponseBody: StandingOrderCallbackResponseBody,
+}
+
+// SimSwap
+
+#[derive(Deserialize, Debug)]
+pub struct SimSwapResponseHeader {
+    pub responseRefID: Option<String>,
+    pub responseCode: Option<String>,
+    pub responseDesc: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SimSwapResponseBody {
+    pub customerNumber: Option<String>,
+    pub lastSwapDate: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SimSwapResponseData {
+    pub ResponseHeader: SimSwapResponseHeader,
+    pub ResponseBody: SimSwapResponseBody,
+}
+
+impl SimSwapResponseData {
+    // Tells whether the last sim swap happened within the given number of days (up to now).
+    // A number that has never been swapped has no last swap date and returns false.
+    pub fn is_swapped_within_days(&self, days: u32) -> Result<bool, MpesaError> {
+        self.is_swapped_within_days_at(days, get_nairobi_time())
+    }
+
+    // Same as is_swapped_within_days, counting back from now (Africa/Nairobi time, like
+    // lastSwapDate)
+    pub fn is_swapped_within_days_at(
+        &self,
+        days: u32,
+        now: chrono::NaiveDateTime,
+    ) -> Result<bool, MpesaError> {
+        let last_swap_date = match &self.ResponseBody.lastSwapDate {
+            Some(x) if !x.trim().is_empty() => x.trim().to_string(),
+            _ => return Ok(false),
+        };
+
+        // lastSwapDate e.g. 2019-10-23 10:40:17 or 2019-10-23
+        let last_swap_date =
+            match chrono::NaiveDateTime::parse_from_str(&last_swap_date, "%Y-%m-%d %H:%M:%S") {
+                Ok(x) => x,
+                Err(_) => match chrono::NaiveDate::parse_from_str(&last_swap_date, "%Y-%m-%d") {
+                    Ok(x) => x.and_hms_opt(0, 0, 0).unwrap_or_default(),
+                    Err(_) => {
+                        return Err(MpesaError::validation(
+                            "last swap date",
+                            "has invalid value",
+                        ))
+                    }
+                },
+            };
+
+        let window_start = now - chrono::Duration::days(days as i64);
+
+        Ok(last_swap_date >= window_start)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SimSwapErrorResponseData {
+    pub requestId: Option<String>,
+    pub errorCode: Option<String>,
+    pub errorMessage: Option<String>,
+}
+
+// CustomerIdentity
+
+#[derive(Deserialize, Debug)]
+pub struct CustomerIdentityResponseData {
+    pub OriginatorConversationID: Option<String>,
+    pub ResponseCode: Option<String>,
+    pub ResponseDescription: Option<String>,
+    pub IsMatch: Option<bool>,
+}
+
+impl CustomerIdentityResponseData {
+    // The identity matches only when the request succeeded and the id details belong to the msisdn
+    pub fn is_match(&self) -> bool {
+        let k = String::from(""); //Default value.
+        let response_code = self.ResponseCode.as_ref().unwrap_or(&k);
+
+        response_code.eq("0") && self.IsMatch.unwrap_or(false)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CustomerIdentityErrorResponseData {
+    pub requestId: Option<String>,
+    pub errorCode: Option<String>,
+    pub errorMessage: Option<String>,
+}
+
+// BusinessTransfer
+// The result and timeout callbacks use BusinessPayBillResultData and BusinessPayBillFailedData
+
+#[derive(Deserialize, Debug)]
+pub struct BusinessTransferResponseData {
+    pub OriginatorConversationID: Option<String>,
+    pub ConversationID: Option<String>,
+    pub ResponseCode: Option<String>,
+    pub ResponseDescription: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BusinessTransferErrorResponseData {
+    pub requestId: Option<String>,
+    pub errorCode: Option<String>,
+    pub errorMessage: Option<String>,
+}
+
+// BillManager
+
+#[derive(Deserialize, Debug)]
+pub struct BillManagerOptInResponseData {
+    pub app_key: Option<String>,
+    pub resmsg: Option<String>,
+    pub rescode: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BillManagerInvoiceResponseData {
+    pub Status_Message: Option<String>,
+    pub resmsg: Option<String>,
+    pub rescode: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BillManagerCancelInvoiceResponseData {
+    pub Status_Message: Option<String>,
+    pub resmsg: Option<String>,
+    pub rescode: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BillManagerReconciliationResponseData {
+    pub resmsg: Option<String>,
+    pub rescode: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BillManagerErrorResponseData {
+    pub requestId: Option<String>,
+    pub errorCode: Option<String>,
+    pub errorMessage: Option<String>,
+}
+
+// Daraja error codes returned when the access token is rejected
+pub(crate) const AUTH_ERROR_CODES: [&str; 3] = ["404.001.03", "404.001.04", "401.002.01"];
+
+pub(crate) trait ErrorResponseData {
+    fn get_error_code(&self) -> Option<String>;
+
+    fn is_auth_error(&self) -> bool {
+        match self.get_error_code() {
+            Some(x) => AUTH_ERROR_CODES.contains(&x.trim()),
+            None => false,
+        }
+    }
+}
+
+macro_rules! impl_error_response_data {
+    ($($x:ty),*) => {
+        $(
+            impl ErrorResponseData for $x {
+                fn get_error_code(&self) -> Option<String> {
+                    self.errorCode.clone()
+                }
+            }
+
+            impl From<$x> for MpesaError {
+                fn from(x: $x) -> Self {
+                    MpesaError::Api {
+                        request_id: x.requestId,
+                        error_code: x.errorCode,
+                        error_message: x.errorMessage,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_error_response_data!(
+    RegisterUrlErrorResponseData,
+    PullTransactionsErrorResponseData,
+    BusinessToCustomerErrorResponseData,
+    CustomerToBusinessPaymentErrorResponseData,
+    CustomerToBusinessPaymentQueryErrorResponseData,
+    CustomerToBusinessSimulateErrorResponseData,
+    BusinessPayBillErrorResponseData,
+    BusinessBuyGoodsErrorResponseData,
+    TransactionStatusErrorResponseData,
+    AccountBalanceErrorResponseData,
+    ReversalErrorResponseData,
+    BusinessExpressCheckoutErrorResponseData,
+    DynamicQrErrorResponseData,
+    RemitTaxErrorResponseData,
+    B2CAccountTopUpErrorResponseData,
+    B2PochiErrorResponseData,
+    StandingOrderErrorResponseData,
+    SimSwapErrorResponseData,
+    CustomerIdentityErrorResponseData,
+    BusinessTransferErrorResponseData,
+    BillManagerErrorResponseData
+);
+
+// This struct holds  Register Url processing data
+/*
+pub struct RegisterUrlInputDetails {
+    //pub access_token: String,
+    //pub api_url: String,
     pub short_code: String,
     pub response_type: String,
     pub confirmation_url: String,
     pub validation_url: String,
 }
-*/
-pub struct RegisterUrlInputDetails {
-    api_url: String,
+*/
+pub struct RegisterUrlInputDetails {
+    api_url: Option<String>,
+    short_code: String,
+    response_type: String,
+    confirmation_url: String,
+    validation_url: String,
+    version: RegisterUrlVersion,
+}
+
+// Version of the register url api, v2 sends the MSISDN masked or hashed in the c2b callbacks
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RegisterUrlVersion {
+    V1,
+    V2,
+}
+
+impl RegisterUrlVersion {
+    pub fn get_endpoint(&self) -> Endpoint {
+        match self {
+            RegisterUrlVersion::V1 => Endpoint::RegisterUrl,
+            RegisterUrlVersion::V2 => Endpoint::RegisterUrlV2,
+        }
+    }
+}
+
+impl RegisterUrlInputDetails {
+    pub fn new(
+        api_url: Option<String>,
+        short_code: String,
+        response_type: String,
+        confirmation_url: String,
+        validation_url: String,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if short_code.is_empty() || short_code.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("short code", "is empty"));
+        }
+
+        if response_type.is_empty() || response_type.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("response type", "is empty"));
+        }
+
+        // Completed, Cancelled (v1 and v2 register url)
+        if response_type.eq_ignore_ascii_case("Completed")
+            || response_type.eq_ignore_ascii_case("Cancelled")
+        {
+            // response type is valid
+        } else {
+            return Err(MpesaError::validation("response type", "has invalid value"));
+        }
+
+        if confirmation_url.is_empty() || confirmation_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("confirmation url", "is empty"));
+        }
+
+        if validation_url.is_empty() || validation_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("validation url", "is empty"));
+        }
+
+        Ok(Self {
+            api_url,
+            short_code,
+            response_type,
+            confirmation_url,
+            validation_url,
+            version: RegisterUrlVersion::V1,
+        })
+    }
+
+    // The v1 register url is used unless another version is set
+    pub fn with_version(mut self, version: RegisterUrlVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_version(&self) -> RegisterUrlVersion {
+        self.version
+    }
+
+    pub fn get_short_code(&self) -> String {
+        let short_code = &self.short_code;
+        short_code.to_string()
+    }
+
+    pub fn get_response_type(&self) -> String {
+        let response_type = &self.response_type;
+        response_type.to_string()
+    }
+
+    pub fn get_confirmation_url(&self) -> String {
+        let confirmation_url = &self.confirmation_url;
+        confirmation_url.to_string()
+    }
+
+    pub fn get_validation_url(&self) -> String {
+        let validation_url = &self.validation_url;
+        validation_url.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CustomerIdType {
+    NationalId,
+    MilitaryId,
+    AlienId,
+    Passport,
+}
+
+impl CustomerIdType {
+    pub fn get_code(&self) -> String {
+        let code = match self {
+            CustomerIdType::NationalId => "01",
+            CustomerIdType::MilitaryId => "02",
+            CustomerIdType::AlienId => "03",
+            CustomerIdType::Passport => "04",
+        };
+
+        code.to_string()
+    }
+}
+
+// This struct holds  Customer Identity (KYC) check processing data
+#[derive(Debug, Clone)]
+pub struct CustomerIdentityInputDetails {
+    api_url: String,
+    _msisdn: u64,
+    id_type: CustomerIdType,
+    id_number: String,
+}
+
+impl CustomerIdentityInputDetails {
+    // The identity check is not part of the public daraja catalog, api_url is the url of the
+    // check agreed with Safaricom for the account.
+    pub fn new(
+        api_url: String,
+        _msisdn: u64,
+        id_type: CustomerIdType,
+        id_number: String,
+    ) -> Result<Self, MpesaError> {
+        if api_url.is_empty() || api_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("api url", "is empty"));
+        }
+
+        if _msisdn == 0 {
+            return Err(MpesaError::validation("msisdn", "has invalid value"));
+        }
+
+        // msisdn (12 digits) e.g. 254708374149
+        if _msisdn.to_string().len() == 12 && _msisdn.to_string().starts_with("254") {
+        } else {
+            return Err(MpesaError::validation("msisdn", "has invalid value"));
+        }
+
+        if id_number.is_empty() || id_number.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("id number", "is empty"));
+        }
+
+        Ok(Self {
+            api_url,
+            _msisdn,
+            id_type,
+            id_number: id_number.trim().to_string(),
+        })
+    }
+
+    pub fn get_api_url(&self) -> String {
+        let api_url = &self.api_url;
+        api_url.to_string()
+    }
+
+    pub fn get_msisdn(&self) -> u64 {
+        let _msisdn = &self._msisdn;
+        *_msisdn
+    }
+
+    pub fn get_id_type(&self) -> CustomerIdType {
+        self.id_type
+    }
+
+    pub fn get_id_number(&self) -> String {
+        let id_number = &self.id_number;
+        id_number.to_string()
+    }
+}
+
+// This struct holds  Business To Customer processing data
+#[derive(Debug)]
+pub struct BusinessToCustomerInputDetails {
+    api_url: Option<String>,
+    originator_conversation_id: String,
+    initiator_name: String,
+    security_credential: String,
+    command_id: String,
+    amount: u32,
+    party_a: u32,
+    party_b: String,
+    _remarks: String,
+    queue_time_out_url: String,
+    result_url: String,
+    _occassion: String,
+    identity_check: Option<CustomerIdentityInputDetails>,
+}
+
+impl BusinessToCustomerInputDetails {
+    // Generates the security credential from the initiator password with the bundled
+    // certificate of the environment
+    pub fn new_with_initiator_password(
+        api_url: Option<String>,
+        environment: &Environment,
+        originator_conversation_id: String,
+        initiator_name: String,
+        initiator_password: String,
+        command_id: String,
+        amount: u32,
+        party_a: u32,
+        party_b: String,
+        _remarks: String,
+        queue_time_out_url: String,
+        result_url: String,
+        _occassion: String,
+    ) -> Result<Self, MpesaError> {
+        let security_credential: String =
+            generate_security_credential_for_environment(&initiator_password, environment)?;
+
+        Self::new(
+            api_url,
+            originator_conversation_id,
+            initiator_name,
+            security_credential,
+            command_id,
+            amount,
+            party_a,
+            party_b,
+            _remarks,
+            queue_time_out_url,
+            result_url,
+            _occassion,
+        )
+    }
+
+    pub fn new(
+        api_url: Option<String>,
+        originator_conversation_id: String,
+        initiator_name: String,
+        security_credential: String,
+        command_id: String,
+        amount: u32,
+        party_a: u32,
+        party_b: String,
+        _remarks: String,
+        queue_time_out_url: String,
+        result_url: String,
+        _occassion: String,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if originator_conversation_id.is_empty()
+            || originator_conversation_id.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation(
+                "originator conversation id",
+                "is empty",
+            ));
+        }
+
+        if initiator_name.is_empty() || initiator_name.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("initiator name", "is empty"));
+        }
+
+        if security_credential.is_empty() || security_credential.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation("security credential", "is empty"));
+        }
+
+        if command_id.is_empty() || command_id.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("command id", "is empty"));
+        }
+
+        // SalaryPayment, BusinessPayment, PromotionPayment
+        if command_id
+            .to_lowercase()
+            .eq_ignore_ascii_case(&String::from("SalaryPayment"))
+            || command_id
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("BusinessPayment"))
+            || command_id
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("PromotionPayment"))
+        {
+            // command id is valid
+        } else {
+            return Err(MpesaError::validation("command id", "has invalid value"));
+        }
+
+        if amount == 0 {
+            return Err(MpesaError::validation("amount", "has invalid value"));
+        }
+
+        if party_a == 0 {
+            return Err(MpesaError::validation("party a", "has invalid value"));
+        }
+
+        // party_a (5-6 digits) e.g. 123454
+        if party_a.to_string().len() == 5 || party_a.to_string().len() == 6 {
+        } else {
+            return Err(MpesaError::validation("party a", "has invalid value"));
+        }
+
+        if party_b.is_empty() || party_b.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("party b", "is empty"));
+        }
+
+        if _remarks.is_empty() || _remarks.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("remarks", "is empty"));
+        }
+
+        if queue_time_out_url.is_empty() || queue_time_out_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("queue_time_out url", "is empty"));
+        }
+
+        if result_url.is_empty() || result_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("result url", "is empty"));
+        }
+
+        // _occassion is optional parameter
+
+        Ok(Self {
+            api_url,
+            originator_conversation_id,
+            initiator_name,
+            security_credential,
+            command_id,
+            amount,
+            party_a,
+            party_b,
+            _remarks,
+            queue_time_out_url,
+            result_url,
+            _occassion,
+            identity_check: None,
+        })
+    }
+
+    // Runs the customer identity (KYC) check before the b2c payment is sent.
+    // The payment is not sent unless the identity matches.
+    pub fn with_identity_check(
+        mut self,
+        identity_check: CustomerIdentityInputDetails,
+    ) -> Result<Self, MpesaError> {
+        if !identity_check
+            .get_msisdn()
+            .to_string()
+            .eq(self.party_b.trim())
+        {
+            return Err(MpesaError::validation(
+                "identity check msisdn",
+                "does not match party b",
+            ));
+        }
+
+        self.identity_check = Some(identity_check);
+
+        Ok(self)
+    }
+
+    pub fn get_identity_check(&self) -> Option<CustomerIdentityInputDetails> {
+        self.identity_check.clone()
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_originator_conversation_id(&self) -> String {
+        let originator_conversation_id = &self.originator_conversation_id;
+        originator_conversation_id.to_string()
+    }
+
+    pub fn get_initiator_name(&self) -> String {
+        let initiator_name = &self.initiator_name;
+        initiator_name.to_string()
+    }
+
+    pub fn get_security_credential(&self) -> String {
+        let security_credential = &self.security_credential;
+        security_credential.to_string()
+    }
+
+    pub fn get_command_id(&self) -> String {
+        let command_id = &self.command_id;
+        command_id.to_string()
+    }
+
+    pub fn get_amount(&self) -> u32 {
+        let amount = &self.amount;
+        *amount
+    }
+
+    pub fn get_party_a(&self) -> u32 {
+        let party_a = &self.party_a;
+        *party_a
+    }
+
+    pub fn get_party_b(&self) -> String {
+        let party_b = &self.party_b;
+        party_b.to_string()
+    }
+
+    pub fn get_remarks(&self) -> String {
+        let _remarks = &self._remarks;
+        _remarks.to_string()
+    }
+
+    pub fn get_queue_time_out_url(&self) -> String {
+        let queue_time_out_url = &self.queue_time_out_url;
+        queue_time_out_url.to_string()
+    }
+
+    pub fn get_result_url(&self) -> String {
+        let result_url = &self.result_url;
+        result_url.to_string()
+    }
+
+    pub fn get_occassion(&self) -> String {
+        let _occassion = &self._occassion;
+        _occassion.to_string()
+    }
+}
+
+#[derive(Debug)]
+pub struct CustomerToBusinessPaymentInputDetails {
+    api_url: Option<String>,
+    business_short_code: String,
+    _password: String,
+    time_stamp: String,
+    transaction_type: String,
+    _amount: u32,
+    party_a: u64,
+    party_b: u32,
+    phone_number: u64,
+    call_back_url: String,
+    account_reference: String,
+    transaction_desc: String,
+}
+
+impl CustomerToBusinessPaymentInputDetails {
+    // Generates the time stamp (Africa/Nairobi time) and the password from the pass key
+    pub fn new_with_pass_key(
+        api_url: Option<String>,
+        business_short_code: String,
+        pass_key: String,
+        transaction_type: String,
+        _amount: u32,
+        party_a: u64,
+        party_b: u32,
+        phone_number: u64,
+        call_back_url: String,
+        account_reference: String,
+        transaction_desc: String,
+    ) -> Result<Self, MpesaError> {
+        if pass_key.is_empty() || pass_key.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("pass key", "is empty"));
+        }
+
+        let time_stamp: String = get_stk_time_stamp();
+        let _password: String = build_stk_password(&business_short_code, &pass_key, &time_stamp);
+
+        Self::new(
+            api_url,
+            business_short_code,
+            _password,
+            time_stamp,
+            transaction_type,
+            _amount,
+            party_a,
+            party_b,
+            phone_number,
+            call_back_url,
+            account_reference,
+            transaction_desc,
+        )
+    }
+
+    pub fn new(
+        api_url: Option<String>,
+        business_short_code: String,
+        _password: String,
+        time_stamp: String,
+        transaction_type: String,
+        _amount: u32,
+        party_a: u64,
+        party_b: u32,
+        phone_number: u64,
+        call_back_url: String,
+        account_reference: String,
+        transaction_desc: String,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if business_short_code.is_empty() || business_short_code.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation("business short code", "is empty"));
+        }
+
+        // business_short_code (5-6 digits) e.g. 123454
+        if business_short_code.len() == 5 || business_short_code.len() == 6 {
+        } else {
+            return Err(MpesaError::validation("party a", "has invalid value"));
+        }
+
+        if _password.is_empty() || _password.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("password", "is empty"));
+        }
+
+        if time_stamp.is_empty() || time_stamp.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("time stamp", "is empty"));
+        }
+
+        // time_stamp (YYYYMMDDHHmmss) e.g. 20230915143020
+        if chrono::NaiveDateTime::parse_from_str(&time_stamp, "%Y%m%d%H%M%S").is_err() {
+            return Err(MpesaError::validation("time stamp", "has invalid value"));
+        }
+
+        // _password = base64(Shortcode+Passkey+Timestamp)
+        if !is_valid_stk_password(&_password, &business_short_code, &time_stamp) {
+            return Err(MpesaError::validation(
+                "password",
+                "does not match time stamp",
+            ));
+        }
+
+        if transaction_type.is_empty() || transaction_type.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("transaction type", "is empty"));
+        }
+
+        // CustomerPayBillOnline, CustomerBuyGoodsOnline
+        if transaction_type
+            .to_lowercase()
+            .eq_ignore_ascii_case(&String::from("CustomerPayBillOnline"))
+            || transaction_type
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("CustomerBuyGoodsOnline"))
+        {
+            // transaction_type is valid
+        } else {
+            return Err(MpesaError::validation(
+                "transaction type",
+                "has invalid value",
+            ));
+        }
+
+        if _amount == 0 {
+            return Err(MpesaError::validation("amount", "has invalid value"));
+        }
+
+        if party_a == 0 {
+            return Err(MpesaError::validation("party a", "has invalid value"));
+        }
+
+        if party_b == 0 {
+            return Err(MpesaError::validation("party b", "has invalid value"));
+        }
+
+        // party_b (5-6 digits) e.g. 123454
+        if party_b.to_string().len() == 5 || party_b.to_string().len() == 6 {
+        } else {
+            return Err(MpesaError::validation("party b", "has invalid value"));
+        }
+
+        if phone_number == 0 {
+            return Err(MpesaError::validation("phone number", "has invalid value"));
+        }
+
+        if call_back_url.is_empty() || call_back_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("call_back url", "is empty"));
+        }
+
+        if account_reference.is_empty() || account_reference.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("account reference", "is empty"));
+        }
+        // account_reference has a max length of 12 characters
+        else if account_reference.trim().len() > 0 && account_reference.trim().len() <= 12 {
+            // account_reference is valid
+        } else {
+            return Err(MpesaError::validation(
+                "account reference",
+                "has invalid length",
+            ));
+        }
+
+        if transaction_desc.is_empty() || transaction_desc.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("transaction desc", "is empty"));
+        }
+        // transaction_desc has a max length of 13 characters
+        else if transaction_desc.trim().len() > 0 && transaction_desc.trim().len() <= 13 {
+        } else {
+            return Err(MpesaError::validation(
+                "transaction desc",
+                "has invalid value/length",
+            ));
+        }
+
+        Ok(Self {
+            api_url,
+            business_short_code,
+            _password,
+            time_stamp,
+            transaction_type,
+            _amount,
+            party_a,
+            party_b,
+            phone_number,
+            call_back_url,
+            account_reference,
+            transaction_desc,
+        })
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_business_short_code(&self) -> String {
+        let business_short_code = &self.business_short_code;
+        business_short_code.to_string()
+    }
+
+    pub fn get_password(&self) -> String {
+        let _password = &self._password;
+        _password.to_string()
+    }
+
+    pub fn get_time_stamp(&self) -> String {
+        let time_stamp = &self.time_stamp;
+        time_stamp.to_string()
+    }
+
+    pub fn get_transaction_type(&self) -> String {
+        let transaction_type = &self.transaction_type;
+        transaction_type.to_string()
+    }
+
+    pub fn get_amount(&self) -> u32 {
+        let _amount = &self._amount;
+        *_amount
+    }
+
+    pub fn get_party_a(&self) -> u64 {
+        let party_a = &self.party_a;
+        *party_a
+    }
+
+    pub fn get_party_b(&self) -> u32 {
+        let party_b = &self.party_b;
+        *party_b
+    }
+
+    pub fn get_phone_number(&self) -> u64 {
+        let phone_number = &self.phone_number;
+        *phone_number
+    }
+
+    pub fn get_call_back_url(&self) -> String {
+        let call_back_url = &self.call_back_url;
+        call_back_url.to_string()
+    }
+
+    pub fn get_account_reference(&self) -> String {
+        let account_reference = &self.account_reference;
+        account_reference.to_string()
+    }
+
+    pub fn get_transaction_desc(&self) -> String {
+        let transaction_desc = &self.transaction_desc;
+        transaction_desc.to_string()
+    }
+}
+
+#[derive(Debug)]
+pub struct CustomerToBusinessPaymentQueryInputDetails {
+    api_url: Option<String>,
+    business_short_code: String,
+    _password: String,
+    time_stamp: String,
+    checkout_request_id: String,
+}
+
+impl CustomerToBusinessPaymentQueryInputDetails {
+    pub fn new(
+        api_url: Option<String>,
+        business_short_code: String,
+        _password: String,
+        time_stamp: String,
+        checkout_request_id: String,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if business_short_code.is_empty() || business_short_code.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation("business short code", "is empty"));
+        }
+
+        // business_short_code (5-6 digits) e.g. 123454
+        if business_short_code.len() == 5 || business_short_code.len() == 6 {
+        } else {
+            return Err(MpesaError::validation(
+                "business short code",
+                "has invalid value",
+            ));
+        }
+
+        if _password.is_empty() || _password.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("password", "is empty"));
+        }
+
+        if time_stamp.is_empty() || time_stamp.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("time stamp", "is empty"));
+        }
+
+        if checkout_request_id.is_empty() || checkout_request_id.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation("checkout request id", "is empty"));
+        }
+
+        Ok(Self {
+            api_url,
+            business_short_code,
+            _password,
+            time_stamp,
+            checkout_request_id,
+        })
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_business_short_code(&self) -> String {
+        let business_short_code = &self.business_short_code;
+        business_short_code.to_string()
+    }
+
+    pub fn get_password(&self) -> String {
+        let _password = &self._password;
+        _password.to_string()
+    }
+
+    pub fn get_time_stamp(&self) -> String {
+        let time_stamp = &self.time_stamp;
+        time_stamp.to_string()
+    }
+
+    pub fn get_checkout_request_id(&self) -> String {
+        let checkout_request_id = &self.checkout_request_id;
+        checkout_request_id.to_string()
+    }
+}
+
+// This struct holds  C2B Simulate processing data (sandbox only)
+#[derive(Debug)]
+pub struct CustomerToBusinessSimulateInputDetails {
+    api_url: Option<String>,
+    short_code: String,
+    command_id: String,
+    _amount: u32,
+    _msisdn: u64,
+    bill_ref_number: String,
+}
+
+impl CustomerToBusinessSimulateInputDetails {
+    pub fn new(
+        api_url: Option<String>,
+        short_code: String,
+        command_id: String,
+        _amount: u32,
+        _msisdn: u64,
+        bill_ref_number: String,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if short_code.is_empty() || short_code.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("short code", "is empty"));
+        }
+
+        // short_code (5-6 digits) e.g. 123454
+        if short_code.len() == 5 || short_code.len() == 6 {
+        } else {
+            return Err(MpesaError::validation("short code", "has invalid value"));
+        }
+
+        if command_id.is_empty() || command_id.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("command id", "is empty"));
+        }
+
+        // CustomerPayBillOnline, CustomerBuyGoodsOnline
+        let is_pay_bill = command_id
+            .to_lowercase()
+            .eq_ignore_ascii_case(&String::from("CustomerPayBillOnline"));
+
+        if is_pay_bill
+            || command_id
+                .to_lowercase()
+                .eq_ignore_ascii_case(&String::from("CustomerBuyGoodsOnline"))
+        {
+            // command id is valid
+        } else {
+            return Err(MpesaError::validation("command id", "has invalid value"));
+        }
+
+        if _amount == 0 {
+            return Err(MpesaError::validation("amount", "has invalid value"));
+        }
+
+        if _msisdn == 0 {
+            return Err(MpesaError::validation("msisdn", "has invalid value"));
+        }
+
+        // msisdn (12 digits) e.g. 254708374149
+        if _msisdn.to_string().len() == 12 {
+        } else {
+            return Err(MpesaError::validation("msisdn", "has invalid value"));
+        }
+
+        // bill_ref_number is only required for paybill, till numbers have no account
+        if is_pay_bill
+            && (bill_ref_number.is_empty() || bill_ref_number.replace(" ", "").trim().len() == 0)
+        {
+            return Err(MpesaError::validation("bill ref number", "is empty"));
+        }
+
+        Ok(Self {
+            api_url,
+            short_code,
+            command_id,
+            _amount,
+            _msisdn,
+            bill_ref_number,
+        })
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_short_code(&self) -> String {
+        let short_code = &self.short_code;
+        short_code.to_string()
+    }
+
+    pub fn get_command_id(&self) -> String {
+        let command_id = &self.command_id;
+        command_id.to_string()
+    }
+
+    pub fn get_amount(&self) -> u32 {
+        let _amount = &self._amount;
+        *_amount
+    }
+
+    pub fn get_msisdn(&self) -> u64 {
+        let _msisdn = &self._msisdn;
+        *_msisdn
+    }
+
+    pub fn get_bill_ref_number(&self) -> String {
+        let bill_ref_number = &self.bill_ref_number;
+        bill_ref_number.to_string()
+    }
+}
+
+#[derive(Debug)]
+pub struct BusinessPayBillInputDetails {
+    api_url: Option<String>,
+    _initiator: String,
+    security_credential: String,
+    command_id: String,
+    sender_identifier_type: String,
+    reciever_identifier_type: String,
+    _amount: u32,
+    party_a: String,
+    party_b: String,
+    account_reference: String,
+    _requester: String,
+    _remarks: String,
+    queue_time_out_url: String,
+    result_url: String,
+}
+
+impl BusinessPayBillInputDetails {
+    pub fn new_with_initiator_password(
+        api_url: Option<String>,
+        environment: &Environment,
+        _initiator: String,
+        initiator_password: String,
+        command_id: String,
+        sender_identifier_type: String,
+        reciever_identifier_type: String,
+        _amount: u32,
+        party_a: String,
+        party_b: String,
+        account_reference: String,
+        _requester: String,
+        _remarks: String,
+        queue_time_out_url: String,
+        result_url: String,
+    ) -> Result<Self, MpesaError> {
+        let security_credential: String =
+            generate_security_credential_for_environment(&initiator_password, environment)?;
+
+        Self::new(
+            api_url,
+            _initiator,
+            security_credential,
+            command_id,
+            sender_identifier_type,
+            reciever_identifier_type,
+            _amount,
+            party_a,
+            party_b,
+            account_reference,
+            _requester,
+            _remarks,
+            queue_time_out_url,
+            result_url,
+        )
+    }
+
+    pub fn new(
+        api_url: Option<String>,
+        _initiator: String,
+        security_credential: String,
+        command_id: String,
+        sender_identifier_type: String,
+        reciever_identifier_type: String,
+        _amount: u32,
+        party_a: String,
+        party_b: String,
+        account_reference: String,
+        _requester: String,
+        _remarks: String,
+        queue_time_out_url: String,
+        result_url: String,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if _initiator.is_empty() || _initiator.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("initiator", "is empty"));
+        }
+
+        if security_credential.is_empty() || security_credential.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation("security credential", "is empty"));
+        }
+
+        if command_id.is_empty() || command_id.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("command id", "is empty"));
+        }
+
+        // BusinessPayBill
+        if command_id
+            .to_lowercase()
+            .eq_ignore_ascii_case(&String::from("BusinessPayBill"))
+        {
+            // command id is valid
+        } else {
+            return Err(MpesaError::validation("command", "has invalid value"));
+        }
+
+        if sender_identifier_type.is_empty()
+            || sender_identifier_type.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation("sender identifier type", "is empty"));
+        }
+
+        if reciever_identifier_type.is_empty()
+            || reciever_identifier_type.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation(
+                "reciever identifier type",
+                "is empty",
+            ));
+        }
+
+        if _amount == 0 {
+            return Err(MpesaError::validation("amount", "has invalid value"));
+        }
+
+        if party_a.is_empty() || party_a.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("party a", "is empty"));
+        }
+
+        // party_a (5-6 digits) e.g. 123454
+        if party_a.to_string().len() == 5 || party_a.to_string().len() == 6 {
+        } else {
+            return Err(MpesaError::validation("party a", "has invalid value"));
+        }
+
+        if party_b.is_empty() || party_b.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("party b", "is empty"));
+        }
+
+        // party_b (5-6 digits) e.g. 123454
+        if party_b.to_string().len() == 5 || party_b.to_string().len() == 6 {
+        } else {
+            return Err(MpesaError::validation("party b", "has invalid value"));
+        }
+
+        if account_reference.is_empty() || account_reference.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("account reference", "is empty"));
+        }
+        // account_reference has a max length of 13 characters
+        else if account_reference.trim().len() > 0 && account_reference.trim().len() <= 13 {
+            // account_reference is valid
+        } else {
+            return Err(MpesaError::validation(
+                "account reference",
+                "has invalid length",
+            ));
+        }
+
+        if _requester.is_empty() || _requester.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("_requester", "is empty"));
+        }
+
+        if _remarks.is_empty() || _remarks.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("remarks", "is empty"));
+        }
+        // _remarks has a max length of 100 characters
+        else if _remarks.trim().len() > 0 && _remarks.trim().len() <= 100 {
+            // _remarks is valid
+        } else {
+            return Err(MpesaError::validation("remarks", "has invalid length"));
+        }
+
+        if queue_time_out_url.is_empty() || queue_time_out_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("queue_time_out url", "is empty"));
+        }
+
+        if result_url.is_empty() || result_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("result url", "is empty"));
+        }
+
+        Ok(Self {
+            api_url,
+            _initiator,
+            security_credential,
+            command_id,
+            sender_identifier_type,
+            reciever_identifier_type,
+            _amount,
+            party_a,
+            party_b,
+            account_reference,
+            _requester,
+            _remarks,
+            queue_time_out_url,
+            result_url,
+        })
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_initiator(&self) -> String {
+        let _initiator = &self._initiator;
+        _initiator.to_string()
+    }
+
+    pub fn get_security_credential(&self) -> String {
+        let security_credential = &self.security_credential;
+        security_credential.to_string()
+    }
+
+    pub fn get_command_id(&self) -> String {
+        let command_id = &self.command_id;
+        command_id.to_string()
+    }
+
+    pub fn get_sender_identifier_type(&self) -> String {
+        let sender_identifier_type = &self.sender_identifier_type;
+        sender_identifier_type.to_string()
+    }
+
+    pub fn get_reciever_identifier_type(&self) -> String {
+        let reciever_identifier_type = &self.reciever_identifier_type;
+        reciever_identifier_type.to_string()
+    }
+
+    pub fn get_amount(&self) -> u32 {
+        let _amount = &self._amount;
+        *_amount
+    }
+
+    pub fn get_party_a(&self) -> String {
+        let party_a = &self.party_a;
+        party_a.to_string()
+    }
+
+    pub fn get_party_b(&self) -> String {
+        let party_b = &self.party_b;
+        party_b.to_string()
+    }
+
+    pub fn get_account_reference(&self) -> String {
+        let account_reference = &self.account_reference;
+        account_reference.to_string()
+    }
+
+    pub fn get_requester(&self) -> String {
+        let _requester = &self._requester;
+        _requester.to_string()
+    }
+
+    pub fn get_remarks(&self) -> String {
+        let _remarks = &self._remarks;
+        _remarks.to_string()
+    }
+
+    pub fn get_queue_time_out_url(&self) -> String {
+        let queue_time_out_url = &self.queue_time_out_url;
+        queue_time_out_url.to_string()
+    }
+
+    pub fn get_result_url(&self) -> String {
+        let result_url = &self.result_url;
+        result_url.to_string()
+    }
+}
+
+#[derive(Debug)]
+pub struct BusinessBuyGoodsInputDetails {
+    api_url: Option<String>,
+    _initiator: String,
+    security_credential: String,
+    command_id: String,
+    sender_identifier_type: String,
+    reciever_identifier_type: String,
+    _amount: u32,
+    party_a: String,
+    party_b: String,
+    account_reference: String,
+    _requester: String,
+    _remarks: String,
+    queue_time_out_url: String,
+    result_url: String,
+}
+
+impl BusinessBuyGoodsInputDetails {
+    pub fn new_with_initiator_password(
+        api_url: Option<String>,
+        environment: &Environment,
+        _initiator: String,
+        initiator_password: String,
+        command_id: String,
+        sender_identifier_type: String,
+        reciever_identifier_type: String,
+        _amount: u32,
+        party_a: String,
+        party_b: String,
+        account_reference: String,
+        _requester: String,
+        _remarks: String,
+        queue_time_out_url: String,
+        result_url: String,
+    ) -> Result<Self, MpesaError> {
+        let security_credential: String =
+            generate_security_credential_for_environment(&initiator_password, environment)?;
+
+        Self::new(
+            api_url,
+            _initiator,
+            security_credential,
+            command_id,
+            sender_identifier_type,
+            reciever_identifier_type,
+            _amount,
+            party_a,
+            party_b,
+            account_reference,
+            _requester,
+            _remarks,
+            queue_time_out_url,
+            result_url,
+        )
+    }
+
+    pub fn new(
+        api_url: Option<String>,
+        _initiator: String,
+        security_credential: String,
+        command_id: String,
+        sender_identifier_type: String,
+        reciever_identifier_type: String,
+        _amount: u32,
+        party_a: String,
+        party_b: String,
+        account_reference: String,
+        _requester: String,
+        _remarks: String,
+        queue_time_out_url: String,
+        result_url: String,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if _initiator.is_empty() || _initiator.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("initiator", "is empty"));
+        }
+
+        if security_credential.is_empty() || security_credential.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation("security credential", "is empty"));
+        }
+
+        if command_id.is_empty() || command_id.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("command id", "is empty"));
+        }
+
+        // BusinessBuyGoods
+        if command_id
+            .to_lowercase()
+            .eq_ignore_ascii_case(&String::from("BusinessBuyGoods"))
+        {
+            // command id is valid
+        } else {
+            return Err(MpesaError::validation("command", "has invalid value"));
+        }
+
+        if sender_identifier_type.is_empty()
+            || sender_identifier_type.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation("sender identifier type", "is empty"));
+        }
+
+        if reciever_identifier_type.is_empty()
+            || reciever_identifier_type.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation(
+                "reciever identifier type",
+                "is empty",
+            ));
+        }
+
+        if _amount == 0 {
+            return Err(MpesaError::validation("amount", "has invalid value"));
+        }
+
+        if party_a.is_empty() || party_a.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("party a", "is empty"));
+        }
+
+        // party_a (5-6 digits) e.g. 123454
+        if party_a.to_string().len() == 5 || party_a.to_string().len() == 6 {
+        } else {
+            return Err(MpesaError::validation("party a", "has invalid value"));
+        }
+
+        if party_b.is_empty() || party_b.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("party b", "is empty"));
+        }
+
+        // party_b (5-6 digits) e.g. 123454
+        if party_b.to_string().len() == 5 || party_b.to_string().len() == 6 {
+        } else {
+            return Err(MpesaError::validation("party b", "has invalid value"));
+        }
+
+        if account_reference.is_empty() || account_reference.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("account reference", "is empty"));
+        }
+        // account_reference has a max length of 13 characters
+        else if account_reference.trim().len() > 0 && account_reference.trim().len() <= 13 {
+            // account_reference is valid
+        } else {
+            return Err(MpesaError::validation(
+                "account reference",
+                "has invalid length",
+            ));
+        }
+
+        if _requester.is_empty() || _requester.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("_requester", "is empty"));
+        }
+
+        if _remarks.is_empty() || _remarks.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("remarks", "is empty"));
+        }
+        // _remarks has a max length of 100 characters
+        else if _remarks.trim().len() > 0 && _remarks.trim().len() <= 100 {
+            // _remarks is valid
+        } else {
+            return Err(MpesaError::validation("remarks", "has invalid length"));
+        }
+
+        if queue_time_out_url.is_empty() || queue_time_out_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("queue_time_out url", "is empty"));
+        }
+
+        if result_url.is_empty() || result_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("result url", "is empty"));
+        }
+
+        Ok(Self {
+            api_url,
+            _initiator,
+            security_credential,
+            command_id,
+            sender_identifier_type,
+            reciever_identifier_type,
+            _amount,
+            party_a,
+            party_b,
+            account_reference,
+            _requester,
+            _remarks,
+            queue_time_out_url,
+            result_url,
+        })
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_initiator(&self) -> String {
+        let _initiator = &self._initiator;
+        _initiator.to_string()
+    }
+
+    pub fn get_security_credential(&self) -> String {
+        let security_credential = &self.security_credential;
+        security_credential.to_string()
+    }
+
+    pub fn get_command_id(&self) -> String {
+        let command_id = &self.command_id;
+        command_id.to_string()
+    }
+
+    pub fn get_sender_identifier_type(&self) -> String {
+        let sender_identifier_type = &self.sender_identifier_type;
+        sender_identifier_type.to_string()
+    }
+
+    pub fn get_reciever_identifier_type(&self) -> String {
+        let reciever_identifier_type = &self.reciever_identifier_type;
+        reciever_identifier_type.to_string()
+    }
+
+    pub fn get_amount(&self) -> u32 {
+        let _amount = &self._amount;
+        *_amount
+    }
+
+    pub fn get_party_a(&self) -> String {
+        let party_a = &self.party_a;
+        party_a.to_string()
+    }
+
+    pub fn get_party_b(&self) -> String {
+        let party_b = &self.party_b;
+        party_b.to_string()
+    }
+
+    pub fn get_account_reference(&self) -> String {
+        let account_reference = &self.account_reference;
+        account_reference.to_string()
+    }
+
+    pub fn get_requester(&self) -> String {
+        let _requester = &self._requester;
+        _requester.to_string()
+    }
+
+    pub fn get_remarks(&self) -> String {
+        let _remarks = &self._remarks;
+        _remarks.to_string()
+    }
+
+    pub fn get_queue_time_out_url(&self) -> String {
+        let queue_time_out_url = &self.queue_time_out_url;
+        queue_time_out_url.to_string()
+    }
+
+    pub fn get_result_url(&self) -> String {
+        let result_url = &self.result_url;
+        result_url.to_string()
+    }
+}
+
+// This struct holds  Transaction Status processing data
+#[derive(Debug)]
+pub struct TransactionStatusInputDetails {
+    api_url: Option<String>,
+    _initiator: String,
+    security_credential: String,
+    command_id: String,
+    transaction_id: String,
+    original_conversation_id: String,
+    party_a: String,
+    identifier_type: String,
+    result_url: String,
+    queue_time_out_url: String,
+    _remarks: String,
+    _occasion: String,
+}
+
+impl TransactionStatusInputDetails {
+    pub fn new(
+        api_url: Option<String>,
+        _initiator: String,
+        security_credential: String,
+        command_id: String,
+        transaction_id: String,
+        original_conversation_id: String,
+        party_a: String,
+        identifier_type: String,
+        result_url: String,
+        queue_time_out_url: String,
+        _remarks: String,
+        _occasion: String,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if _initiator.is_empty() || _initiator.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("initiator", "is empty"));
+        }
+
+        if security_credential.is_empty() || security_credential.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation("security credential", "is empty"));
+        }
+
+        if command_id.is_empty() || command_id.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("command id", "is empty"));
+        }
+
+        // TransactionStatusQuery
+        if command_id
+            .to_lowercase()
+            .eq_ignore_ascii_case(&String::from("TransactionStatusQuery"))
+        {
+            // command id is valid
+        } else {
+            return Err(MpesaError::validation("command id", "has invalid value"));
+        }
+
+        // the transaction is looked up either by its receipt (transaction_id)
+        // or by the OriginatorConversationID of the original request
+        if (transaction_id.is_empty() || transaction_id.replace(" ", "").trim().len() == 0)
+            && (original_conversation_id.is_empty()
+                || original_conversation_id.replace(" ", "").trim().len() == 0)
+        {
+            return Err(MpesaError::validation(
+                "transaction id and original conversation id",
+                "are empty",
+            ));
+        }
+
+        if party_a.is_empty() || party_a.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("party a", "is empty"));
+        }
+
+        if identifier_type.is_empty() || identifier_type.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("identifier type", "is empty"));
+        }
+
+        // 1 - MSISDN, 2 - Till Number, 4 - Organization short code
+        if identifier_type.eq("1") || identifier_type.eq("2") || identifier_type.eq("4") {
+            // identifier type is valid
+        } else {
+            return Err(MpesaError::validation(
+                "identifier type",
+                "has invalid value",
+            ));
+        }
+
+        if result_url.is_empty() || result_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("result url", "is empty"));
+        }
+
+        if queue_time_out_url.is_empty() || queue_time_out_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("queue_time_out url", "is empty"));
+        }
+
+        if _remarks.is_empty() || _remarks.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("remarks", "is empty"));
+        }
+        // _remarks has a max length of 100 characters
+        else if _remarks.trim().len() > 0 && _remarks.trim().len() <= 100 {
+            // _remarks is valid
+        } else {
+            return Err(MpesaError::validation("remarks", "has invalid length"));
+        }
+
+        // _occasion is optional parameter
+
+        Ok(Self {
+            api_url,
+            _initiator,
+            security_credential,
+            command_id,
+            transaction_id,
+            original_conversation_id,
+            party_a,
+            identifier_type,
+            result_url,
+            queue_time_out_url,
+            _remarks,
+            _occasion,
+        })
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_initiator(&self) -> String {
+        let _initiator = &self._initiator;
+        _initiator.to_string()
+    }
+
+    pub fn get_security_credential(&self) -> String {
+        let security_credential = &self.security_credential;
+        security_credential.to_string()
+    }
+
+    pub fn get_command_id(&self) -> String {
+        let command_id = &self.command_id;
+        command_id.to_string()
+    }
+
+    pub fn get_transaction_id(&self) -> String {
+        let transaction_id = &self.transaction_id;
+        transaction_id.to_string()
+    }
+
+    pub fn get_original_conversation_id(&self) -> String {
+        let original_conversation_id = &self.original_conversation_id;
+        original_conversation_id.to_string()
+    }
+
+    pub fn get_party_a(&self) -> String {
+        let party_a = &self.party_a;
+        party_a.to_string()
+    }
+
+    pub fn get_identifier_type(&self) -> String {
+        let identifier_type = &self.identifier_type;
+        identifier_type.to_string()
+    }
+
+    pub fn get_result_url(&self) -> String {
+        let result_url = &self.result_url;
+        result_url.to_string()
+    }
+
+    pub fn get_queue_time_out_url(&self) -> String {
+        let queue_time_out_url = &self.queue_time_out_url;
+        queue_time_out_url.to_string()
+    }
+
+    pub fn get_remarks(&self) -> String {
+        let _remarks = &self._remarks;
+        _remarks.to_string()
+    }
+
+    pub fn get_occasion(&self) -> String {
+        let _occasion = &self._occasion;
+        _occasion.to_string()
+    }
+}
+
+// This struct holds  Account Balance processing data
+#[derive(Debug)]
+pub struct AccountBalanceInputDetails {
+    api_url: Option<String>,
+    _initiator: String,
+    security_credential: String,
+    command_id: String,
+    party_a: String,
+    identifier_type: String,
+    _remarks: String,
+    queue_time_out_url: String,
+    result_url: String,
+}
+
+impl AccountBalanceInputDetails {
+    pub fn new(
+        api_url: Option<String>,
+        _initiator: String,
+        security_credential: String,
+        command_id: String,
+        party_a: String,
+        identifier_type: String,
+        _remarks: String,
+        queue_time_out_url: String,
+        result_url: String,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if _initiator.is_empty() || _initiator.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("initiator", "is empty"));
+        }
+
+        if security_credential.is_empty() || security_credential.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation("security credential", "is empty"));
+        }
+
+        if command_id.is_empty() || command_id.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("command id", "is empty"));
+        }
+
+        // AccountBalance
+        if command_id
+            .to_lowercase()
+            .eq_ignore_ascii_case(&String::from("AccountBalance"))
+        {
+            // command id is valid
+        } else {
+            return Err(MpesaError::validation("command id", "has invalid value"));
+        }
+
+        if party_a.is_empty() || party_a.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("party a", "is empty"));
+        }
+
+        // party_a (5-6 digits) e.g. 123454
+        if party_a.to_string().len() == 5 || party_a.to_string().len() == 6 {
+        } else {
+            return Err(MpesaError::validation("party a", "has invalid value"));
+        }
+
+        if identifier_type.is_empty() || identifier_type.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("identifier type", "is empty"));
+        }
+
+        // 2 - Till Number, 4 - Organization short code
+        if identifier_type.eq("2") || identifier_type.eq("4") {
+            // identifier type is valid
+        } else {
+            return Err(MpesaError::validation(
+                "identifier type",
+                "has invalid value",
+            ));
+        }
+
+        if _remarks.is_empty() || _remarks.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("remarks", "is empty"));
+        }
+        // _remarks has a max length of 100 characters
+        else if _remarks.trim().len() > 0 && _remarks.trim().len() <= 100 {
+            // _remarks is valid
+        } else {
+            return Err(MpesaError::validation("remarks", "has invalid length"));
+        }
+
+        if queue_time_out_url.is_empty() || queue_time_out_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("queue_time_out url", "is empty"));
+        }
+
+        if result_url.is_empty() || result_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("result url", "is empty"));
+        }
+
+        Ok(Self {
+            api_url,
+            _initiator,
+            security_credential,
+            command_id,
+            party_a,
+            identifier_type,
+            _remarks,
+            queue_time_out_url,
+            result_url,
+        })
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_initiator(&self) -> String {
+        let _initiator = &self._initiator;
+        _initiator.to_string()
+    }
+
+    pub fn get_security_credential(&self) -> String {
+        let security_credential = &self.security_credential;
+        security_credential.to_string()
+    }
+
+    pub fn get_command_id(&self) -> String {
+        let command_id = &self.command_id;
+        command_id.to_string()
+    }
+
+    pub fn get_party_a(&self) -> String {
+        let party_a = &self.party_a;
+        party_a.to_string()
+    }
+
+    pub fn get_identifier_type(&self) -> String {
+        let identifier_type = &self.identifier_type;
+        identifier_type.to_string()
+    }
+
+    pub fn get_remarks(&self) -> String {
+        let _remarks = &self._remarks;
+        _remarks.to_string()
+    }
+
+    pub fn get_queue_time_out_url(&self) -> String {
+        let queue_time_out_url = &self.queue_time_out_url;
+        queue_time_out_url.to_string()
+    }
+
+    pub fn get_result_url(&self) -> String {
+        let result_url = &self.result_url;
+        result_url.to_string()
+    }
+}
+
+// This struct holds  Transaction Reversal processing data
+#[derive(Debug)]
+pub struct ReversalInputDetails {
+    api_url: Option<String>,
+    _initiator: String,
+    security_credential: String,
+    command_id: String,
+    transaction_id: String,
+    _amount: u32,
+    receiver_party: String,
+    reciever_identifier_type: String,
+    result_url: String,
+    queue_time_out_url: String,
+    _remarks: String,
+    _occasion: String,
+}
+
+impl ReversalInputDetails {
+    pub fn new(
+        api_url: Option<String>,
+        _initiator: String,
+        security_credential: String,
+        command_id: String,
+        transaction_id: String,
+        _amount: u32,
+        receiver_party: String,
+        reciever_identifier_type: String,
+        result_url: String,
+        queue_time_out_url: String,
+        _remarks: String,
+        _occasion: String,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if _initiator.is_empty() || _initiator.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("initiator", "is empty"));
+        }
+
+        if security_credential.is_empty() || security_credential.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation("security credential", "is empty"));
+        }
+
+        if command_id.is_empty() || command_id.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("command id", "is empty"));
+        }
+
+        // TransactionReversal
+        if command_id
+            .to_lowercase()
+            .eq_ignore_ascii_case(&String::from("TransactionReversal"))
+        {
+            // command id is valid
+        } else {
+            return Err(MpesaError::validation("command id", "has invalid value"));
+        }
+
+        if transaction_id.is_empty() || transaction_id.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("transaction id", "is empty"));
+        }
+
+        if _amount == 0 {
+            return Err(MpesaError::validation("amount", "has invalid value"));
+        }
+
+        if receiver_party.is_empty() || receiver_party.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("receiver party", "is empty"));
+        }
+
+        // receiver_party (5-6 digits) e.g. 123454
+        if receiver_party.to_string().len() == 5 || receiver_party.to_string().len() == 6 {
+        } else {
+            return Err(MpesaError::validation(
+                "receiver party",
+                "has invalid value",
+            ));
+        }
+
+        if reciever_identifier_type.is_empty()
+            || reciever_identifier_type.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation(
+                "reciever identifier type",
+                "is empty",
+            ));
+        }
+
+        // 11 - Organization short code (the only type accepted for reversals)
+        if reciever_identifier_type.eq("11") {
+            // reciever identifier type is valid
+        } else {
+            return Err(MpesaError::validation(
+                "reciever identifier type",
+                "has invalid value",
+            ));
+        }
+
+        if result_url.is_empty() || result_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("result url", "is empty"));
+        }
+
+        if queue_time_out_url.is_empty() || queue_time_out_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("queue_time_out url", "is empty"));
+        }
+
+        if _remarks.is_empty() || _remarks.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("remarks", "is empty"));
+        }
+        // _remarks has a max length of 100 characters
+        else if _remarks.trim().len() > 0 && _remarks.trim().len() <= 100 {
+            // _remarks is valid
+        } else {
+            return Err(MpesaError::validation("remarks", "has invalid length"));
+        }
+
+        // _occasion is optional parameter
+
+        Ok(Self {
+            api_url,
+            _initiator,
+            security_credential,
+            command_id,
+            transaction_id,
+            _amount,
+            receiver_party,
+            reciever_identifier_type,
+            result_url,
+            queue_time_out_url,
+            _remarks,
+            _occasion,
+        })
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_initiator(&self) -> String {
+        let _initiator = &self._initiator;
+        _initiator.to_string()
+    }
+
+    pub fn get_security_credential(&self) -> String {
+        let security_credential = &self.security_credential;
+        security_credential.to_string()
+    }
+
+    pub fn get_command_id(&self) -> String {
+        let command_id = &self.command_id;
+        command_id.to_string()
+    }
+
+    pub fn get_transaction_id(&self) -> String {
+        let transaction_id = &self.transaction_id;
+        transaction_id.to_string()
+    }
+
+    pub fn get_amount(&self) -> u32 {
+        let _amount = &self._amount;
+        *_amount
+    }
+
+    pub fn get_receiver_party(&self) -> String {
+        let receiver_party = &self.receiver_party;
+        receiver_party.to_string()
+    }
+
+    pub fn get_reciever_identifier_type(&self) -> String {
+        let reciever_identifier_type = &self.reciever_identifier_type;
+        reciever_identifier_type.to_string()
+    }
+
+    pub fn get_result_url(&self) -> String {
+        let result_url = &self.result_url;
+        result_url.to_string()
+    }
+
+    pub fn get_queue_time_out_url(&self) -> String {
+        let queue_time_out_url = &self.queue_time_out_url;
+        queue_time_out_url.to_string()
+    }
+
+    pub fn get_remarks(&self) -> String {
+        let _remarks = &self._remarks;
+        _remarks.to_string()
+    }
+
+    pub fn get_occasion(&self) -> String {
+        let _occasion = &self._occasion;
+        _occasion.to_string()
+    }
+}
+
+// This struct holds  B2B Express Checkout (USSD push to till) processing data
+#[derive(Debug)]
+pub struct BusinessExpressCheckoutInputDetails {
+    api_url: Option<String>,
+    primary_short_code: String,
+    receiver_short_code: String,
+    _amount: u32,
+    payment_ref: String,
+    callback_url: String,
+    partner_name: String,
+    request_ref_id: String,
+}
+
+impl BusinessExpressCheckoutInputDetails {
+    pub fn new(
+        api_url: Option<String>,
+        primary_short_code: String,
+        receiver_short_code: String,
+        _amount: u32,
+        payment_ref: String,
+        callback_url: String,
+        partner_name: String,
+        request_ref_id: String,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if primary_short_code.is_empty() || primary_short_code.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("primary short code", "is empty"));
+        }
+
+        // primary_short_code is the merchant till (5-7 digits) e.g. 000001
+        if primary_short_code.len() >= 5 && primary_short_code.len() <= 7 {
+        } else {
+            return Err(MpesaError::validation(
+                "primary short code",
+                "has invalid value",
+            ));
+        }
+
+        if receiver_short_code.is_empty() || receiver_short_code.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation("receiver short code", "is empty"));
+        }
+
+        // receiver_short_code (5-7 digits) e.g. 000002
+        if receiver_short_code.len() >= 5 && receiver_short_code.len() <= 7 {
+        } else {
+            return Err(MpesaError::validation(
+                "receiver short code",
+                "has invalid value",
+            ));
+        }
+
+        if _amount == 0 {
+            return Err(MpesaError::validation("amount", "has invalid value"));
+        }
+
+        if payment_ref.is_empty() || payment_ref.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("payment ref", "is empty"));
+        }
+
+        if callback_url.is_empty() || callback_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("callback url", "is empty"));
+        }
+
+        if partner_name.is_empty() || partner_name.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("partner name", "is empty"));
+        }
+
+        if request_ref_id.is_empty() || request_ref_id.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("request ref id", "is empty"));
+        }
+
+        Ok(Self {
+            api_url,
+            primary_short_code,
+            receiver_short_code,
+            _amount,
+            payment_ref,
+            callback_url,
+            partner_name,
+            request_ref_id,
+        })
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_primary_short_code(&self) -> String {
+        let primary_short_code = &self.primary_short_code;
+        primary_short_code.to_string()
+    }
+
+    pub fn get_receiver_short_code(&self) -> String {
+        let receiver_short_code = &self.receiver_short_code;
+        receiver_short_code.to_string()
+    }
+
+    pub fn get_amount(&self) -> u32 {
+        let _amount = &self._amount;
+        *_amount
+    }
+
+    pub fn get_payment_ref(&self) -> String {
+        let payment_ref = &self.payment_ref;
+        payment_ref.to_string()
+    }
+
+    pub fn get_callback_url(&self) -> String {
+        let callback_url = &self.callback_url;
+        callback_url.to_string()
+    }
+
+    pub fn get_partner_name(&self) -> String {
+        let partner_name = &self.partner_name;
+        partner_name.to_string()
+    }
+
+    pub fn get_request_ref_id(&self) -> String {
+        let request_ref_id = &self.request_ref_id;
+        request_ref_id.to_string()
+    }
+}
+
+// This struct holds  Dynamic QR processing data
+#[derive(Debug)]
+pub struct DynamicQrInputDetails {
+    api_url: Option<String>,
+    merchant_name: String,
+    ref_no: String,
+    _amount: u32,
+    trx_code: String,
+    _cpi: String,
+    _size: String,
+}
+
+impl DynamicQrInputDetails {
+    pub fn new(
+        api_url: Option<String>,
+        merchant_name: String,
+        ref_no: String,
+        _amount: u32,
+        trx_code: String,
+        _cpi: String,
+        _size: String,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if merchant_name.is_empty() || merchant_name.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("merchant name", "is empty"));
+        }
+
+        if ref_no.is_empty() || ref_no.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("ref no", "is empty"));
+        }
+
+        if _amount == 0 {
+            return Err(MpesaError::validation("amount", "has invalid value"));
+        }
+
+        if trx_code.is_empty() || trx_code.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("trx code", "is empty"));
+        }
+
+        if _cpi.is_empty() || _cpi.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("cpi", "is empty"));
+        }
+
+        if !_cpi.chars().all(|c| c.is_ascii_digit()) {
+            return Err(MpesaError::validation("cpi", "has invalid value"));
+        }
+
+        // BG - Pay Merchant (Buy Goods), CPI is the till number
+        // WA - Withdraw Cash at Agent Till, CPI is the agent till number
+        // PB - Paybill or Business number, CPI is the paybill number
+        // SM - Send Money (Mobile number), CPI is the msisdn e.g. 254708374149
+        // SB - Sent to Business, CPI is the business msisdn e.g. 254708374149
+        if trx_code.eq_ignore_ascii_case(&String::from("BG"))
+            || trx_code.eq_ignore_ascii_case(&String::from("WA"))
+            || trx_code.eq_ignore_ascii_case(&String::from("PB"))
+        {
+            // _cpi (5-7 digits) e.g. 373132
+            if _cpi.len() >= 5 && _cpi.len() <= 7 {
+            } else {
+                return Err(MpesaError::validation(
+                    "cpi",
+                    "has invalid value for trx code",
+                ));
+            }
+        } else if trx_code.eq_ignore_ascii_case(&String::from("SM"))
+            || trx_code.eq_ignore_ascii_case(&String::from("SB"))
+        {
+            // _cpi (12 digits) e.g. 254708374149
+            if _cpi.len() == 12 && _cpi.starts_with("254") {
+            } else {
+                return Err(MpesaError::validation(
+                    "cpi",
+                    "has invalid value for trx code",
+                ));
+            }
+        } else {
+            return Err(MpesaError::validation("trx code", "has invalid value"));
+        }
+
+        if _size.is_empty() || _size.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("size", "is empty"));
+        }
+
+        // _size is the width/height of the QR image in pixels e.g. 300
+        match _size.trim().parse::<u32>() {
+            Ok(x) if x > 0 => {}
+            _ => return Err(MpesaError::validation("size", "has invalid value")),
+        }
+
+        Ok(Self {
+            api_url,
+            merchant_name,
+            ref_no,
+            _amount,
+            trx_code: trx_code.to_uppercase(),
+            _cpi,
+            _size,
+        })
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_merchant_name(&self) -> String {
+        let merchant_name = &self.merchant_name;
+        merchant_name.to_string()
+    }
+
+    pub fn get_ref_no(&self) -> String {
+        let ref_no = &self.ref_no;
+        ref_no.to_string()
+    }
+
+    pub fn get_amount(&self) -> u32 {
+        let _amount = &self._amount;
+        *_amount
+    }
+
+    pub fn get_trx_code(&self) -> String {
+        let trx_code = &self.trx_code;
+        trx_code.to_string()
+    }
+
+    pub fn get_cpi(&self) -> String {
+        let _cpi = &self._cpi;
+        _cpi.to_string()
+    }
+
+    pub fn get_size(&self) -> String {
+        let _size = &self._size;
+        _size.to_string()
+    }
+}
+
+const REMIT_TAX_COMMAND_ID: &str = "PayTaxToKRA";
+const REMIT_TAX_IDENTIFIER_TYPE: &str = "4";
+const KRA_SHORT_CODE: &str = "572572";
+
+// This struct holds  Tax Remittance (to KRA) processing data
+#[derive(Debug)]
+pub struct RemitTaxInputDetails {
+    api_url: Option<String>,
+    _initiator: String,
+    security_credential: String,
+    _amount: u32,
+    party_a: String,
+    account_reference: String,
+    _remarks: String,
+    queue_time_out_url: String,
+    result_url: String,
+    party_b: String,
+    sender_identifier_type: String,
+    reciever_identifier_type: String,
+}
+
+impl RemitTaxInputDetails {
+    pub fn new(
+        api_url: Option<String>,
+        _initiator: String,
+        security_credential: String,
+        _amount: u32,
+        party_a: String,
+        account_reference: String,
+        _remarks: String,
+        queue_time_out_url: String,
+        result_url: String,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if _initiator.is_empty() || _initiator.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("initiator", "is empty"));
+        }
+
+        if security_credential.is_empty() || security_credential.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation("security credential", "is empty"));
+        }
+
+        if _amount == 0 {
+            return Err(MpesaError::validation("amount", "has invalid value"));
+        }
+
+        if party_a.is_empty() || party_a.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("party a", "is empty"));
+        }
+
+        // party_a (5-6 digits) e.g. 123454
+        if party_a.to_string().len() == 5 || party_a.to_string().len() == 6 {
+        } else {
+            return Err(MpesaError::validation("party a", "has invalid value"));
+        }
+
+        // account_reference is the payment registration number (PRN) issued by KRA
+        if account_reference.is_empty() || account_reference.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("account reference", "is empty"));
+        }
+        // PRN is numeric and has a max length of 13 characters
+        else if account_reference.trim().len() <= 13
+            && account_reference.trim().chars().all(|c| c.is_ascii_digit())
+        {
+            // account_reference is valid
+        } else {
+            return Err(MpesaError::validation(
+                "account reference",
+                "has invalid prn",
+            ));
+        }
+
+        if _remarks.is_empty() || _remarks.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("remarks", "is empty"));
+        }
+        // _remarks has a max length of 100 characters
+        else if _remarks.trim().len() > 0 && _remarks.trim().len() <= 100 {
+            // _remarks is valid
+        } else {
+            return Err(MpesaError::validation("remarks", "has invalid length"));
+        }
+
+        if queue_time_out_url.is_empty() || queue_time_out_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("queue_time_out url", "is empty"));
+        }
+
+        if result_url.is_empty() || result_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("result url", "is empty"));
+        }
+
+        Ok(Self {
+            api_url,
+            _initiator,
+            security_credential,
+            _amount,
+            party_a,
+            account_reference: account_reference.trim().to_string(),
+            _remarks,
+            queue_time_out_url,
+            result_url,
+            party_b: KRA_SHORT_CODE.to_string(),
+            sender_identifier_type: REMIT_TAX_IDENTIFIER_TYPE.to_string(),
+            reciever_identifier_type: REMIT_TAX_IDENTIFIER_TYPE.to_string(),
+        })
+    }
+
+    // The KRA short code is used unless another party b is set
+    pub fn with_party_b(mut self, party_b: String) -> Result<Self, MpesaError> {
+        if party_b.is_empty() || party_b.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("party b", "is empty"));
+        }
+
+        // party_b (5-6 digits) e.g. 572572
+        let party_b = party_b.trim().to_string();
+        if (party_b.len() == 5 || party_b.len() == 6) && party_b.chars().all(|c| c.is_ascii_digit())
+        {
+            // party_b is valid
+        } else {
+            return Err(MpesaError::validation("party b", "has invalid value"));
+        }
+
+        self.party_b = party_b;
+
+        Ok(self)
+    }
+
+    // Both identifier types default to 4 (organization short code)
+    pub fn with_identifier_types(
+        mut self,
+        sender_identifier_type: String,
+        reciever_identifier_type: String,
+    ) -> Result<Self, MpesaError> {
+        if sender_identifier_type.is_empty()
+            || sender_identifier_type.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation("sender identifier type", "is empty"));
+        }
+
+        if reciever_identifier_type.is_empty()
+            || reciever_identifier_type.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation(
+                "reciever identifier type",
+                "is empty",
+            ));
+        }
+
+        self.sender_identifier_type = sender_identifier_type.trim().to_string();
+        self.reciever_identifier_type = reciever_identifier_type.trim().to_string();
+
+        Ok(self)
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_initiator(&self) -> String {
+        let _initiator = &self._initiator;
+        _initiator.to_string()
+    }
+
+    pub fn get_security_credential(&self) -> String {
+        let security_credential = &self.security_credential;
+        security_credential.to_string()
+    }
+
+    pub fn get_command_id(&self) -> String {
+        REMIT_TAX_COMMAND_ID.to_string()
+    }
+
+    pub fn get_sender_identifier_type(&self) -> String {
+        let sender_identifier_type = &self.sender_identifier_type;
+        sender_identifier_type.to_string()
+    }
+
+    pub fn get_reciever_identifier_type(&self) -> String {
+        let reciever_identifier_type = &self.reciever_identifier_type;
+        reciever_identifier_type.to_string()
+    }
+
+    pub fn get_amount(&self) -> u32 {
+        let _amount = &self._amount;
+        *_amount
+    }
+
+    pub fn get_party_a(&self) -> String {
+        let party_a = &self.party_a;
+        party_a.to_string()
+    }
+
+    pub fn get_party_b(&self) -> String {
+        let party_b = &self.party_b;
+        party_b.to_string()
+    }
+
+    pub fn get_account_reference(&self) -> String {
+        let account_reference = &self.account_reference;
+        account_reference.to_string()
+    }
+
+    pub fn get_remarks(&self) -> String {
+        let _remarks = &self._remarks;
+        _remarks.to_string()
+    }
+
+    pub fn get_queue_time_out_url(&self) -> String {
+        let queue_time_out_url = &self.queue_time_out_url;
+        queue_time_out_url.to_string()
+    }
+
+    pub fn get_result_url(&self) -> String {
+        let result_url = &self.result_url;
+        result_url.to_string()
+    }
+}
+
+// Bill Manager accepts at most 1000 invoices per bulk invoicing request
+const BILL_MANAGER_MAX_BULK_INVOICES: usize = 1000;
+
+// This struct holds  Bill Manager opt-in (and opt-in details update) processing data
+#[derive(Debug)]
+pub struct BillManagerOptInInputDetails {
+    api_url: Option<String>,
+    short_code: String,
+    _email: String,
+    official_contact: String,
+    send_reminders: bool,
+    _logo: String,
+    callback_url: String,
+}
+
+impl BillManagerOptInInputDetails {
+    pub fn new(
+        api_url: Option<String>,
+        short_code: String,
+        _email: String,
+        official_contact: String,
+        send_reminders: bool,
+        _logo: String,
+        callback_url: String,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if short_code.is_empty() || short_code.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("short code", "is empty"));
+        }
+
+        // short_code (5-6 digits) e.g. 123454
+        if short_code.len() == 5 || short_code.len() == 6 {
+        } else {
+            return Err(MpesaError::validation("short code", "has invalid value"));
+        }
+
+        if _email.is_empty() || _email.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("email", "is empty"));
+        }
+
+        if !_email.contains('@') {
+            return Err(MpesaError::validation("email", "has invalid value"));
+        }
+
+        if official_contact.is_empty() || official_contact.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("official contact", "is empty"));
+        }
+
+        // _logo is optional parameter
+
+        if callback_url.is_empty() || callback_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("callback url", "is empty"));
+        }
+
+        Ok(Self {
+            api_url,
+            short_code,
+            _email,
+            official_contact,
+            send_reminders,
+            _logo,
+            callback_url,
+        })
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_short_code(&self) -> String {
+        let short_code = &self.short_code;
+        short_code.to_string()
+    }
+
+    pub fn get_email(&self) -> String {
+        let _email = &self._email;
+        _email.to_string()
+    }
+
+    pub fn get_official_contact(&self) -> String {
+        let official_contact = &self.official_contact;
+        official_contact.to_string()
+    }
+
+    // 1 - send reminders, 0 - do not send reminders
+    pub fn get_send_reminders(&self) -> String {
+        if self.send_reminders {
+            String::from("1")
+        } else {
+            String::from("0")
+        }
+    }
+
+    pub fn get_logo(&self) -> String {
+        let _logo = &self._logo;
+        _logo.to_string()
+    }
+
+    pub fn get_callback_url(&self) -> String {
+        let callback_url = &self.callback_url;
+        callback_url.to_string()
+    }
+}
+
+#[derive(Debug)]
+pub struct BillManagerInvoiceItemDetails {
+    item_name: String,
+    _amount: u32,
+}
+
+impl BillManagerInvoiceItemDetails {
+    pub fn new(item_name: String, _amount: u32) -> Result<Self, MpesaError> {
+        if item_name.is_empty() || item_name.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("item name", "is empty"));
+        }
+
+        if _amount == 0 {
+            return Err(MpesaError::validation("item amount", "has invalid value"));
+        }
+
+        Ok(Self { item_name, _amount })
+    }
+
+    pub fn get_item_name(&self) -> String {
+        let item_name = &self.item_name;
+        item_name.to_string()
+    }
+
+    pub fn get_amount(&self) -> u32 {
+        let _amount = &self._amount;
+        *_amount
+    }
+}
+
+#[derive(Debug)]
+pub struct BillManagerInvoiceDetails {
+    external_reference: String,
+    billed_full_name: String,
+    billed_phone_number: String,
+    billed_period: String,
+    invoice_name: String,
+    due_date: String,
+    account_reference: String,
+    _amount: u32,
+    invoice_items: Vec<BillManagerInvoiceItemDetails>,
+}
+
+impl BillManagerInvoiceDetails {
+    pub fn new(
+        external_reference: String,
+        billed_full_name: String,
+        billed_phone_number: String,
+        billed_period: String,
+        invoice_name: String,
+        due_date: String,
+        account_reference: String,
+        _amount: u32,
+        invoice_items: Vec<BillManagerInvoiceItemDetails>,
+    ) -> Result<Self, MpesaError> {
+        if external_reference.is_empty() || external_reference.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("external reference", "is empty"));
+        }
+
+        if billed_full_name.is_empty() || billed_full_name.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("billed full name", "is empty"));
+        }
+
+        if billed_phone_number.is_empty() || billed_phone_number.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation("billed phone number", "is empty"));
+        }
+
+        // billed_phone_number e.g. 0722000000 or 254722000000
+        if billed_phone_number.chars().all(|c| c.is_ascii_digit())
+            && ((billed_phone_number.len() == 10 && billed_phone_number.starts_with('0'))
+                || (billed_phone_number.len() == 12 && billed_phone_number.starts_with("254")))
+        {
+        } else {
+            return Err(MpesaError::validation(
+                "billed phone number",
+                "has invalid value",
+            ));
+        }
+
+        // billed_period e.g. August 2021
+        if billed_period.is_empty() || billed_period.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("billed period", "is empty"));
+        }
+
+        if invoice_name.is_empty() || invoice_name.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("invoice name", "is empty"));
+        }
+
+        if due_date.is_empty() || due_date.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("due date", "is empty"));
+        }
+
+        // due_date (YYYY-MM-DD) e.g. 2021-10-12
+        if chrono::NaiveDate::parse_from_str(&due_date, "%Y-%m-%d").is_err() {
+            return Err(MpesaError::validation("due date", "has invalid value"));
+        }
+
+        if account_reference.is_empty() || account_reference.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("account reference", "is empty"));
+        }
+
+        if _amount == 0 {
+            return Err(MpesaError::validation("amount", "has invalid value"));
+        }
+
+        // invoice_items is optional parameter
+
+        Ok(Self {
+            external_reference,
+            billed_full_name,
+            billed_phone_number,
+            billed_period,
+            invoice_name,
+            due_date,
+            account_reference,
+            _amount,
+            invoice_items,
+        })
+    }
+
+    pub fn get_external_reference(&self) -> String {
+        let external_reference = &self.external_reference;
+        external_reference.to_string()
+    }
+
+    pub fn get_billed_full_name(&self) -> String {
+        let billed_full_name = &self.billed_full_name;
+        billed_full_name.to_string()
+    }
+
+    pub fn get_billed_phone_number(&self) -> String {
+        let billed_phone_number = &self.billed_phone_number;
+        billed_phone_number.to_string()
+    }
+
+    pub fn get_billed_period(&self) -> String {
+        let billed_period = &self.billed_period;
+        billed_period.to_string()
+    }
+
+    pub fn get_invoice_name(&self) -> String {
+        let invoice_name = &self.invoice_name;
+        invoice_name.to_string()
+    }
+
+    pub fn get_due_date(&self) -> String {
+        let due_date = &self.due_date;
+        due_date.to_string()
+    }
+
+    pub fn get_account_reference(&self) -> String {
+        let account_reference = &self.account_reference;
+        account_reference.to_string()
+    }
+
+    pub fn get_amount(&self) -> u32 {
+        let _amount = &self._amount;
+        *_amount
+    }
+
+    pub fn get_invoice_items(&self) -> &Vec<BillManagerInvoiceItemDetails> {
+        &self.invoice_items
+    }
+}
+
+#[derive(Debug)]
+pub struct BillManagerSingleInvoiceInputDetails {
+    api_url: Option<String>,
+    _invoice: BillManagerInvoiceDetails,
+}
+
+impl BillManagerSingleInvoiceInputDetails {
+    pub fn new(
+        api_url: Option<String>,
+        _invoice: BillManagerInvoiceDetails,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        Ok(Self { api_url, _invoice })
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_invoice(&self) -> &BillManagerInvoiceDetails {
+        &self._invoice
+    }
+}
+
+#[derive(Debug)]
+pub struct BillManagerBulkInvoiceInputDetails {
+    api_url: Option<String>,
+    _invoices: Vec<BillManagerInvoiceDetails>,
+}
+
+impl BillManagerBulkInvoiceInputDetails {
+    pub fn new(
+        api_url: Option<String>,
+        _invoices: Vec<BillManagerInvoiceDetails>,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if _invoices.is_empty() {
+            return Err(MpesaError::validation("invoices", "is empty"));
+        }
+
+        if _invoices.len() > BILL_MANAGER_MAX_BULK_INVOICES {
+            return Err(MpesaError::validation(
+                "invoices",
+                "has more than 1000 entries",
+            ));
+        }
+
+        // external_reference identifies each invoice, it must not repeat in a batch
+        let mut external_references = std::collections::HashSet::new();
+        for _invoice in _invoices.iter() {
+            if !external_references.insert(_invoice.get_external_reference()) {
+                return Err(MpesaError::validation(
+                    "invoices",
+                    "has duplicate external reference",
+                ));
+            }
+        }
+
+        Ok(Self { api_url, _invoices })
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_invoices(&self) -> &Vec<BillManagerInvoiceDetails> {
+        &self._invoices
+    }
+}
+
+#[derive(Debug)]
+pub struct BillManagerCancelInvoiceInputDetails {
+    api_url: Option<String>,
+    external_reference: String,
+}
+
+impl BillManagerCancelInvoiceInputDetails {
+    pub fn new(api_url: Option<String>, external_reference: String) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if external_reference.is_empty() || external_reference.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("external reference", "is empty"));
+        }
+
+        Ok(Self {
+            api_url,
+            external_reference,
+        })
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_external_reference(&self) -> String {
+        let external_reference = &self.external_reference;
+        external_reference.to_string()
+    }
+}
+
+#[derive(Debug)]
+pub struct BillManagerCancelBulkInvoiceInputDetails {
+    api_url: Option<String>,
+    external_references: Vec<String>,
+}
+
+impl BillManagerCancelBulkInvoiceInputDetails {
+    pub fn new(
+        api_url: Option<String>,
+        external_references: Vec<String>,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if external_references.is_empty() {
+            return Err(MpesaError::validation("external references", "is empty"));
+        }
+
+        if external_references.len() > BILL_MANAGER_MAX_BULK_INVOICES {
+            return Err(MpesaError::validation(
+                "external references",
+                "has more than 1000 entries",
+            ));
+        }
+
+        for external_reference in external_references.iter() {
+            if external_reference.is_empty()
+                || external_reference.replace(" ", "").trim().len() == 0
+            {
+                return Err(MpesaError::validation("external reference", "is empty"));
+            }
+        }
+
+        Ok(Self {
+            api_url,
+            external_references,
+        })
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_external_references(&self) -> &Vec<String> {
+        &self.external_references
+    }
+}
+
+// This struct holds  Bill Manager reconciliation (payment acknowledgement) processing data
+#[derive(Debug)]
+pub struct BillManagerReconciliationInputDetails {
+    api_url: Option<String>,
+    payment_date: String,
+    paid_amount: u32,
+    account_reference: String,
+    transaction_id: String,
+    phone_number: String,
+    full_name: String,
+    invoice_name: String,
+    external_reference: String,
+}
+
+impl BillManagerReconciliationInputDetails {
+    pub fn new(
+        api_url: Option<String>,
+        payment_date: String,
+        paid_amount: u32,
+        account_reference: String,
+        transaction_id: String,
+        phone_number: String,
+        full_name: String,
+        invoice_name: String,
+        external_reference: String,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if payment_date.is_empty() || payment_date.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("payment date", "is empty"));
+        }
+
+        // payment_date (YYYY-MM-DD) e.g. 2021-10-01
+        if chrono::NaiveDate::parse_from_str(&payment_date, "%Y-%m-%d").is_err() {
+            return Err(MpesaError::validation("payment date", "has invalid value"));
+        }
+
+        if paid_amount == 0 {
+            return Err(MpesaError::validation("paid amount", "has invalid value"));
+        }
+
+        if account_reference.is_empty() || account_reference.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("account reference", "is empty"));
+        }
+
+        if transaction_id.is_empty() || transaction_id.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("transaction id", "is empty"));
+        }
+
+        if phone_number.is_empty() || phone_number.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("phone number", "is empty"));
+        }
+
+        if full_name.is_empty() || full_name.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("full name", "is empty"));
+        }
+
+        if invoice_name.is_empty() || invoice_name.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("invoice name", "is empty"));
+        }
+
+        if external_reference.is_empty() || external_reference.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("external reference", "is empty"));
+        }
+
+        Ok(Self {
+            api_url,
+            payment_date,
+            paid_amount,
+            account_reference,
+            transaction_id,
+            phone_number,
+            full_name,
+            invoice_name,
+            external_reference,
+        })
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_payment_date(&self) -> String {
+        let payment_date = &self.payment_date;
+        payment_date.to_string()
+    }
+
+    pub fn get_paid_amount(&self) -> u32 {
+        let paid_amount = &self.paid_amount;
+        *paid_amount
+    }
+
+    pub fn get_account_reference(&self) -> String {
+        let account_reference = &self.account_reference;
+        account_reference.to_string()
+    }
+
+    pub fn get_transaction_id(&self) -> String {
+        let transaction_id = &self.transaction_id;
+        transaction_id.to_string()
+    }
+
+    pub fn get_phone_number(&self) -> String {
+        let phone_number = &self.phone_number;
+        phone_number.to_string()
+    }
+
+    pub fn get_full_name(&self) -> String {
+        let full_name = &self.full_name;
+        full_name.to_string()
+    }
+
+    pub fn get_invoice_name(&self) -> String {
+        let invoice_name = &self.invoice_name;
+        invoice_name.to_string()
+    }
+
+    pub fn get_external_reference(&self) -> String {
+        let external_reference = &self.external_reference;
+        external_reference.to_string()
+    }
+}
+
+const PULL_TRANSACTIONS_REQUEST_TYPE: &str = "Pull";
+const PULL_TRANSACTIONS_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+#[derive(Debug)]
+pub struct PullTransactionsRegisterInputDetails {
+    api_url: Option<String>,
     short_code: String,
-    response_type: String,
-    confirmation_url: String,
-    validation_url: String,
+    nominated_number: String,
+    callback_url: String,
 }
 
-impl RegisterUrlInputDetails {
+impl PullTransactionsRegisterInputDetails {
     pub fn new(
-        api_url: String,
+        api_url: Option<String>,
         short_code: String,
-        response_type: String,
-        confirmation_url: String,
-        validation_url: String,
-    ) -> Result<Self, String> {
-        if api_url.is_empty() || api_url.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("api url is empty"));
+        nominated_number: String,
+        callback_url: String,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
         }
 
         if short_code.is_empty() || short_code.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("short code is empty"));
+            return Err(MpesaError::validation("short code", "is empty"));
         }
 
-        if response_type.is_empty() || response_type.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("response type is empty"));
+        // short_code (5-6 digits) e.g. 123454
+        if short_code.len() == 5 || short_code.len() == 6 {
+        } else {
+            return Err(MpesaError::validation("short code", "has invalid value"));
         }
 
-        if confirmation_url.is_empty() || confirmation_url.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("confirmation url is empty"));
+        if nominated_number.is_empty() || nominated_number.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("nominated number", "is empty"));
         }
 
-        if validation_url.is_empty() || validation_url.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("validation url is empty"));
+        if callback_url.is_empty() || callback_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("callback url", "is empty"));
         }
 
         Ok(Self {
             api_url,
             short_code,
-            response_type,
-            confirmation_url,
-            validation_url,
+            nominated_number,
+            callback_url,
         })
     }
 
-    pub fn get_api_url(&self) -> String {
-        let api_url = &self.api_url;
-        api_url.to_string()
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
     }
 
     pub fn get_short_code(&self) -> String {
@@ -440,144 +4465,453 @@ impl RegisterUrlInputDetails {
         short_code.to_string()
     }
 
-    pub fn get_response_type(&self) -> String {
-        let response_type = &self.response_type;
-        response_type.to_string()
+    pub fn get_request_type(&self) -> String {
+        PULL_TRANSACTIONS_REQUEST_TYPE.to_string()
     }
 
-    pub fn get_confirmation_url(&self) -> String {
-        let confirmation_url = &self.confirmation_url;
-        confirmation_url.to_string()
+    pub fn get_nominated_number(&self) -> String {
+        let nominated_number = &self.nominated_number;
+        nominated_number.to_string()
+    }
+
+    pub fn get_callback_url(&self) -> String {
+        let callback_url = &self.callback_url;
+        callback_url.to_string()
+    }
+}
+
+#[derive(Debug)]
+pub struct PullTransactionsQueryInputDetails {
+    api_url: Option<String>,
+    short_code: String,
+    start_date: String,
+    end_date: String,
+    offset_value: u32,
+}
+
+impl PullTransactionsQueryInputDetails {
+    pub fn new(
+        api_url: Option<String>,
+        short_code: String,
+        start_date: String,
+        end_date: String,
+        offset_value: u32,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if short_code.is_empty() || short_code.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("short code", "is empty"));
+        }
+
+        // short_code (5-6 digits) e.g. 123454
+        if short_code.len() == 5 || short_code.len() == 6 {
+        } else {
+            return Err(MpesaError::validation("short code", "has invalid value"));
+        }
+
+        if start_date.is_empty() || start_date.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("start date", "is empty"));
+        }
+
+        if end_date.is_empty() || end_date.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("end date", "is empty"));
+        }
+
+        // start_date/end_date (YYYY-MM-DD HH:MM:SS) e.g. 2020-08-04 08:36:00
+        let _start_date =
+            chrono::NaiveDateTime::parse_from_str(&start_date, PULL_TRANSACTIONS_DATE_FORMAT);
+        let _end_date =
+            chrono::NaiveDateTime::parse_from_str(&end_date, PULL_TRANSACTIONS_DATE_FORMAT);
+
+        match (_start_date, _end_date) {
+            (Ok(_start_date), Ok(_end_date)) => {
+                if _end_date < _start_date {
+                    return Err(MpesaError::validation(
+                        "end date",
+                        "is earlier than start date",
+                    ));
+                }
+            }
+            (Err(_), _) => {
+                return Err(MpesaError::validation("start date", "has invalid value"));
+            }
+            (_, Err(_)) => {
+                return Err(MpesaError::validation("end date", "has invalid value"));
+            }
+        }
+
+        Ok(Self {
+            api_url,
+            short_code,
+            start_date,
+            end_date,
+            offset_value,
+        })
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_short_code(&self) -> String {
+        let short_code = &self.short_code;
+        short_code.to_string()
+    }
+
+    pub fn get_start_date(&self) -> String {
+        let start_date = &self.start_date;
+        start_date.to_string()
+    }
+
+    pub fn get_end_date(&self) -> String {
+        let end_date = &self.end_date;
+        end_date.to_string()
+    }
+
+    pub fn get_offset_value(&self) -> u32 {
+        let offset_value = &self.offset_value;
+        *offset_value
+    }
+}
+
+const B2C_ACCOUNT_TOP_UP_COMMAND_ID: &str = "BusinessPayToBulk";
+const B2C_ACCOUNT_TOP_UP_IDENTIFIER_TYPE: &str = "4";
+
+// This struct holds  B2C Account Top Up (working account to B2C utility account) processing data
+#[derive(Debug)]
+pub struct B2CAccountTopUpInputDetails {
+    api_url: Option<String>,
+    _initiator: String,
+    security_credential: String,
+    _amount: u32,
+    party_a: String,
+    party_b: String,
+    account_reference: String,
+    _requester: Option<String>,
+    _remarks: String,
+    queue_time_out_url: String,
+    result_url: String,
+}
+
+impl B2CAccountTopUpInputDetails {
+    pub fn new_with_initiator_password(
+        api_url: Option<String>,
+        environment: &Environment,
+        _initiator: String,
+        initiator_password: String,
+        _amount: u32,
+        party_a: String,
+        party_b: String,
+        account_reference: String,
+        _requester: Option<String>,
+        _remarks: String,
+        queue_time_out_url: String,
+        result_url: String,
+    ) -> Result<Self, MpesaError> {
+        let security_credential: String =
+            generate_security_credential_for_environment(&initiator_password, environment)?;
+
+        Self::new(
+            api_url,
+            _initiator,
+            security_credential,
+            _amount,
+            party_a,
+            party_b,
+            account_reference,
+            _requester,
+            _remarks,
+            queue_time_out_url,
+            result_url,
+        )
+    }
+
+    pub fn new(
+        api_url: Option<String>,
+        _initiator: String,
+        security_credential: String,
+        _amount: u32,
+        party_a: String,
+        party_b: String,
+        account_reference: String,
+        _requester: Option<String>,
+        _remarks: String,
+        queue_time_out_url: String,
+        result_url: String,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if _initiator.is_empty() || _initiator.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("initiator", "is empty"));
+        }
+
+        if security_credential.is_empty() || security_credential.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation("security credential", "is empty"));
+        }
+
+        if _amount == 0 {
+            return Err(MpesaError::validation("amount", "has invalid value"));
+        }
+
+        if party_a.is_empty() || party_a.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("party a", "is empty"));
+        }
+
+        // party_a (5-6 digits) e.g. 123454
+        if party_a.to_string().len() == 5 || party_a.to_string().len() == 6 {
+        } else {
+            return Err(MpesaError::validation("party a", "has invalid value"));
+        }
+
+        if party_b.is_empty() || party_b.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("party b", "is empty"));
+        }
+
+        // party_b is the B2C shortcode (5-6 digits) e.g. 123454
+        if party_b.to_string().len() == 5 || party_b.to_string().len() == 6 {
+        } else {
+            return Err(MpesaError::validation("party b", "has invalid value"));
+        }
+
+        if account_reference.is_empty() || account_reference.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("account reference", "is empty"));
+        }
+        // account_reference has a max length of 13 characters
+        else if account_reference.trim().len() > 0 && account_reference.trim().len() <= 13 {
+            // account_reference is valid
+        } else {
+            return Err(MpesaError::validation(
+                "account reference",
+                "has invalid length",
+            ));
+        }
+
+        // _requester is optional, but must not be blank when provided
+        if let Some(x) = &_requester {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("_requester", "is empty"));
+            }
+        }
+
+        if _remarks.is_empty() || _remarks.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("remarks", "is empty"));
+        }
+        // _remarks has a max length of 100 characters
+        else if _remarks.trim().len() > 0 && _remarks.trim().len() <= 100 {
+            // _remarks is valid
+        } else {
+            return Err(MpesaError::validation("remarks", "has invalid length"));
+        }
+
+        if queue_time_out_url.is_empty() || queue_time_out_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("queue_time_out url", "is empty"));
+        }
+
+        if result_url.is_empty() || result_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("result url", "is empty"));
+        }
+
+        Ok(Self {
+            api_url,
+            _initiator,
+            security_credential,
+            _amount,
+            party_a,
+            party_b,
+            account_reference,
+            _requester,
+            _remarks,
+            queue_time_out_url,
+            result_url,
+        })
+    }
+
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_initiator(&self) -> String {
+        let _initiator = &self._initiator;
+        _initiator.to_string()
+    }
+
+    pub fn get_security_credential(&self) -> String {
+        let security_credential = &self.security_credential;
+        security_credential.to_string()
+    }
+
+    pub fn get_command_id(&self) -> String {
+        B2C_ACCOUNT_TOP_UP_COMMAND_ID.to_string()
+    }
+
+    pub fn get_sender_identifier_type(&self) -> String {
+        B2C_ACCOUNT_TOP_UP_IDENTIFIER_TYPE.to_string()
+    }
+
+    pub fn get_reciever_identifier_type(&self) -> String {
+        B2C_ACCOUNT_TOP_UP_IDENTIFIER_TYPE.to_string()
+    }
+
+    pub fn get_amount(&self) -> u32 {
+        let _amount = &self._amount;
+        *_amount
+    }
+
+    pub fn get_party_a(&self) -> String {
+        let party_a = &self.party_a;
+        party_a.to_string()
+    }
+
+    pub fn get_party_b(&self) -> String {
+        let party_b = &self.party_b;
+        party_b.to_string()
+    }
+
+    pub fn get_account_reference(&self) -> String {
+        let account_reference = &self.account_reference;
+        account_reference.to_string()
+    }
+
+    pub fn get_requester(&self) -> Option<String> {
+        self._requester.clone()
+    }
+
+    pub fn get_remarks(&self) -> String {
+        let _remarks = &self._remarks;
+        _remarks.to_string()
+    }
+
+    pub fn get_queue_time_out_url(&self) -> String {
+        let queue_time_out_url = &self.queue_time_out_url;
+        queue_time_out_url.to_string()
     }
 
-    pub fn get_validation_url(&self) -> String {
-        let validation_url = &self.validation_url;
-        validation_url.to_string()
+    pub fn get_result_url(&self) -> String {
+        let result_url = &self.result_url;
+        result_url.to_string()
     }
 }
 
-// This struct holds  Business To Customer processing data
+const B2_POCHI_COMMAND_ID: &str = "BusinessPayToPochi";
+
+// This struct holds  Business Pay to Pochi la Biashara processing data
 #[derive(Debug)]
-pub struct BusinessToCustomerInputDetails {
-    api_url: String,
+pub struct B2PochiInputDetails {
+    api_url: Option<String>,
     originator_conversation_id: String,
     initiator_name: String,
     security_credential: String,
-    command_id: String,
     amount: u32,
     party_a: u32,
-    party_b: String,
+    _msisdn: u64,
     _remarks: String,
     queue_time_out_url: String,
     result_url: String,
-    _occassion: String,
+    _occasion: String,
 }
 
-impl BusinessToCustomerInputDetails {
+impl B2PochiInputDetails {
     pub fn new(
-        api_url: String,
+        api_url: Option<String>,
         originator_conversation_id: String,
         initiator_name: String,
         security_credential: String,
-        command_id: String,
         amount: u32,
         party_a: u32,
-        party_b: String,
+        _msisdn: u64,
         _remarks: String,
         queue_time_out_url: String,
         result_url: String,
-        _occassion: String,
-    ) -> Result<Self, String> {
-        if api_url.is_empty() || api_url.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("api url is empty"));
+        _occasion: String,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
         }
 
         if originator_conversation_id.is_empty()
             || originator_conversation_id.replace(" ", "").trim().len() == 0
         {
-            return Err(String::from("originator conversation id is empty"));
+            return Err(MpesaError::validation(
+                "originator conversation id",
+                "is empty",
+            ));
         }
 
         if initiator_name.is_empty() || initiator_name.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("initiator name is empty"));
+            return Err(MpesaError::validation("initiator name", "is empty"));
         }
 
         if security_credential.is_empty() || security_credential.replace(" ", "").trim().len() == 0
         {
-            return Err(String::from("security credential is empty"));
-        }
-
-        if command_id.is_empty() || command_id.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("command id is empty"));
-        }
-
-        // SalaryPayment, BusinessPayment, PromotionPayment
-        if command_id
-            .to_lowercase()
-            .eq_ignore_ascii_case(&String::from("SalaryPayment"))
-            || command_id
-                .to_lowercase()
-                .eq_ignore_ascii_case(&String::from("BusinessPayment"))
-            || command_id
-                .to_lowercase()
-                .eq_ignore_ascii_case(&String::from("PromotionPayment"))
-        {
-            // command id is valid
-        } else {
-            return Err(String::from("command id has invalid value"));
+            return Err(MpesaError::validation("security credential", "is empty"));
         }
 
         if amount == 0 {
-            return Err(String::from("amount has invalid value"));
+            return Err(MpesaError::validation("amount", "has invalid value"));
         }
 
         if party_a == 0 {
-            return Err(String::from("party a has invalid value"));
+            return Err(MpesaError::validation("party a", "has invalid value"));
         }
 
         // party_a (5-6 digits) e.g. 123454
         if party_a.to_string().len() == 5 || party_a.to_string().len() == 6 {
         } else {
-            return Err(String::from("party a has invalid value"));
+            return Err(MpesaError::validation("party a", "has invalid value"));
         }
 
-        if party_b.is_empty() || party_b.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("party b is empty"));
+        if _msisdn == 0 {
+            return Err(MpesaError::validation("msisdn", "has invalid value"));
+        }
+
+        // msisdn (12 digits) e.g. 254708374149
+        if _msisdn.to_string().len() == 12 && _msisdn.to_string().starts_with("254") {
+        } else {
+            return Err(MpesaError::validation("msisdn", "has invalid value"));
         }
 
         if _remarks.is_empty() || _remarks.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("remarks is empty"));
+            return Err(MpesaError::validation("remarks", "is empty"));
         }
 
         if queue_time_out_url.is_empty() || queue_time_out_url.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("queue_time_out url is empty"));
+            return Err(MpesaError::validation("queue_time_out url", "is empty"));
         }
 
         if result_url.is_empty() || result_url.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("result url is empty"));
+            return Err(MpesaError::validation("result url", "is empty"));
         }
 
-        // _occassion is optional parameter
+        // _occasion is optional parameter
 
         Ok(Self {
             api_url,
             originator_conversation_id,
             initiator_name,
             security_credential,
-            command_id,
             amount,
             party_a,
-            party_b,
+            _msisdn,
             _remarks,
             queue_time_out_url,
             result_url,
-            _occassion,
+            _occasion,
         })
     }
 
-    pub fn get_api_url(&self) -> String {
-        let api_url = &self.api_url;
-        api_url.to_string()
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
     }
 
     pub fn get_originator_conversation_id(&self) -> String {
@@ -596,8 +4930,7 @@ impl BusinessToCustomerInputDetails {
     }
 
     pub fn get_command_id(&self) -> String {
-        let command_id = &self.command_id;
-        command_id.to_string()
+        B2_POCHI_COMMAND_ID.to_string()
     }
 
     pub fn get_amount(&self) -> u32 {
@@ -611,8 +4944,8 @@ impl BusinessToCustomerInputDetails {
     }
 
     pub fn get_party_b(&self) -> String {
-        let party_b = &self.party_b;
-        party_b.to_string()
+        let _msisdn = &self._msisdn;
+        _msisdn.to_string()
     }
 
     pub fn get_remarks(&self) -> String {
@@ -630,381 +4963,254 @@ impl BusinessToCustomerInputDetails {
         result_url.to_string()
     }
 
-    pub fn get_occassion(&self) -> String {
-        let _occassion = &self._occassion;
-        _occassion.to_string()
+    pub fn get_occasion(&self) -> String {
+        let _occasion = &self._occasion;
+        _occasion.to_string()
     }
 }
 
-#[derive(Debug)]
-pub struct CustomerToBusinessPaymentInputDetails {
-    api_url: String,
-    business_short_code: String,
-    _password: String,
-    time_stamp: String,
-    transaction_type: String,
-    _amount: u32,
-    party_a: u64,
-    party_b: u32,
-    phone_number: u64,
-    call_back_url: String,
-    account_reference: String,
-    transaction_desc: String,
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StandingOrderFrequency {
+    OneOff,
+    Daily,
+    Weekly,
+    Monthly,
+    BiMonthly,
+    Quarterly,
+    HalfYearly,
+    Yearly,
 }
 
-impl CustomerToBusinessPaymentInputDetails {
-    pub fn new(
-        api_url: String,
-        business_short_code: String,
-        _password: String,
-        time_stamp: String,
-        transaction_type: String,
-        _amount: u32,
-        party_a: u64,
-        party_b: u32,
-        phone_number: u64,
-        call_back_url: String,
-        account_reference: String,
-        transaction_desc: String,
-    ) -> Result<Self, String> {
-        if api_url.is_empty() || api_url.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("api url is empty"));
-        }
-
-        if business_short_code.is_empty() || business_short_code.replace(" ", "").trim().len() == 0
-        {
-            return Err(String::from("business short code is empty"));
-        }
-
-        // business_short_code (5-6 digits) e.g. 123454
-        if business_short_code.len() == 5 || business_short_code.len() == 6 {
-        } else {
-            return Err(String::from("party a has invalid value"));
-        }
-
-        if _password.is_empty() || _password.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("password is empty"));
-        }
-
-        if time_stamp.is_empty() || time_stamp.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("time stamp is empty"));
-        }
-
-        if transaction_type.is_empty() || transaction_type.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("transaction type is empty"));
-        }
-
-        // CustomerPayBillOnline, CustomerBuyGoodsOnline
-        if transaction_type
-            .to_lowercase()
-            .eq_ignore_ascii_case(&String::from("CustomerPayBillOnline"))
-            || transaction_type
-                .to_lowercase()
-                .eq_ignore_ascii_case(&String::from("CustomerBuyGoodsOnline"))
-        {
-            // transaction_type is valid
-        } else {
-            return Err(String::from("transaction type has invalid value"));
-        }
-
-        if _amount == 0 {
-            return Err(String::from("amount has invalid value"));
-        }
-
-        if party_a == 0 {
-            return Err(String::from("party a has invalid value"));
-        }
-
-        if party_b == 0 {
-            return Err(String::from("party b has invalid value"));
-        }
-
-        // party_b (5-6 digits) e.g. 123454
-        if party_b.to_string().len() == 5 || party_b.to_string().len() == 6 {
-        } else {
-            return Err(String::from("party b has invalid value"));
-        }
-
-        if phone_number == 0 {
-            return Err(String::from("phone number has invalid value"));
-        }
-
-        if call_back_url.is_empty() || call_back_url.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("call_back url is empty"));
-        }
-
-        if account_reference.is_empty() || account_reference.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("account reference is empty"));
-        }
-        // account_reference has a max length of 12 characters
-        else if account_reference.trim().len() > 0 && account_reference.trim().len() <= 12 {
-            // account_reference is valid
-        } else {
-            return Err(String::from("account reference has invalid length"));
-        }
-
-        if transaction_desc.is_empty() || transaction_desc.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("transaction desc is empty"));
-        }
-        // transaction_desc has a max length of 13 characters
-        else if transaction_desc.trim().len() > 0 && transaction_desc.trim().len() <= 13 {
-        } else {
-            return Err(String::from("transaction desc has invalid value/length"));
-        }
-
-        Ok(Self {
-            api_url,
-            business_short_code,
-            _password,
-            time_stamp,
-            transaction_type,
-            _amount,
-            party_a,
-            party_b,
-            phone_number,
-            call_back_url,
-            account_reference,
-            transaction_desc,
-        })
-    }
-
-    pub fn get_api_url(&self) -> String {
-        let api_url = &self.api_url;
-        api_url.to_string()
-    }
-
-    pub fn get_business_short_code(&self) -> String {
-        let business_short_code = &self.business_short_code;
-        business_short_code.to_string()
-    }
-
-    pub fn get_password(&self) -> String {
-        let _password = &self._password;
-        _password.to_string()
-    }
-
-    pub fn get_time_stamp(&self) -> String {
-        let time_stamp = &self.time_stamp;
-        time_stamp.to_string()
-    }
-
-    pub fn get_transaction_type(&self) -> String {
-        let transaction_type = &self.transaction_type;
-        transaction_type.to_string()
-    }
-
-    pub fn get_amount(&self) -> u32 {
-        let _amount = &self._amount;
-        *_amount
-    }
-
-    pub fn get_party_a(&self) -> u64 {
-        let party_a = &self.party_a;
-        *party_a
-    }
-
-    pub fn get_party_b(&self) -> u32 {
-        let party_b = &self.party_b;
-        *party_b
-    }
-
-    pub fn get_phone_number(&self) -> u64 {
-        let phone_number = &self.phone_number;
-        *phone_number
-    }
-
-    pub fn get_call_back_url(&self) -> String {
-        let call_back_url = &self.call_back_url;
-        call_back_url.to_string()
-    }
-
-    pub fn get_account_reference(&self) -> String {
-        let account_reference = &self.account_reference;
-        account_reference.to_string()
-    }
-
-    pub fn get_transaction_desc(&self) -> String {
-        let transaction_desc = &self.transaction_desc;
-        transaction_desc.to_string()
+impl StandingOrderFrequency {
+    pub fn get_code(&self) -> String {
+        let code = match self {
+            StandingOrderFrequency::OneOff => "1",
+            StandingOrderFrequency::Daily => "2",
+            StandingOrderFrequency::Weekly => "3",
+            StandingOrderFrequency::Monthly => "4",
+            StandingOrderFrequency::BiMonthly => "5",
+            StandingOrderFrequency::Quarterly => "6",
+            StandingOrderFrequency::HalfYearly => "7",
+            StandingOrderFrequency::Yearly => "8",
+        };
+
+        code.to_string()
     }
 }
 
+const STANDING_ORDER_PAY_BILL: &str = "Standing Order Customer Pay Bill";
+const STANDING_ORDER_PAY_MERCHANT: &str = "Standing Order Customer Pay Marchant";
+const STANDING_ORDER_DATE_FORMAT: &str = "%Y%m%d";
+
+// This struct holds  Standing Order (Lipa na M-Pesa Ratiba) processing data
 #[derive(Debug)]
-pub struct BusinessPayBillInputDetails {
-    api_url: String,
-    _initiator: String,
-    security_credential: String,
-    command_id: String,
-    sender_identifier_type: String,
-    reciever_identifier_type: String,
+pub struct StandingOrderInputDetails {
+    api_url: Option<String>,
+    standing_order_name: String,
+    start_date: String,
+    end_date: String,
+    business_short_code: String,
+    transaction_type: String,
+    receiver_party_identifier_type: String,
     _amount: u32,
-    party_a: String,
-    party_b: String,
+    party_a: u64,
+    callback_url: String,
     account_reference: String,
-    _requester: String,
-    _remarks: String,
-    queue_time_out_url: String,
-    result_url: String,
-}
-
-impl BusinessPayBillInputDetails {
-    pub fn new(
-        api_url: String,
-        _initiator: String,
-        security_credential: String,
-        command_id: String,
-        sender_identifier_type: String,
-        reciever_identifier_type: String,
+    transaction_desc: String,
+    _frequency: StandingOrderFrequency,
+}
+
+impl StandingOrderInputDetails {
+    pub fn new(
+        api_url: Option<String>,
+        standing_order_name: String,
+        start_date: String,
+        end_date: String,
+        business_short_code: String,
+        transaction_type: String,
+        receiver_party_identifier_type: String,
         _amount: u32,
-        party_a: String,
-        party_b: String,
+        party_a: u64,
+        callback_url: String,
         account_reference: String,
-        _requester: String,
-        _remarks: String,
-        queue_time_out_url: String,
-        result_url: String,
-    ) -> Result<Self, String> {
-        if api_url.is_empty() || api_url.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("api url is empty"));
-        }
-
-        if _initiator.is_empty() || _initiator.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("initiator is empty"));
+        transaction_desc: String,
+        _frequency: StandingOrderFrequency,
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
         }
 
-        if security_credential.is_empty() || security_credential.replace(" ", "").trim().len() == 0
+        if standing_order_name.is_empty() || standing_order_name.replace(" ", "").trim().len() == 0
         {
-            return Err(String::from("security credential is empty"));
+            return Err(MpesaError::validation("standing order name", "is empty"));
         }
 
-        if command_id.is_empty() || command_id.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("command id is empty"));
+        if start_date.is_empty() || start_date.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("start date", "is empty"));
         }
 
-        // BusinessPayBill
-        if command_id
-            .to_lowercase()
-            .eq_ignore_ascii_case(&String::from("BusinessPayBill"))
-        {
-            // command id is valid
-        } else {
-            return Err(String::from("command has invalid value"));
+        if end_date.is_empty() || end_date.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("end date", "is empty"));
         }
 
-        if sender_identifier_type.is_empty()
-            || sender_identifier_type.replace(" ", "").trim().len() == 0
-        {
-            return Err(String::from("sender identifier type is empty"));
+        // start_date/end_date (YYYYMMDD) e.g. 20240905
+        let _start_date =
+            chrono::NaiveDate::parse_from_str(&start_date, STANDING_ORDER_DATE_FORMAT);
+        let _end_date = chrono::NaiveDate::parse_from_str(&end_date, STANDING_ORDER_DATE_FORMAT);
+
+        match (_start_date, _end_date) {
+            (Ok(_start_date), Ok(_end_date)) => {
+                if _end_date <= _start_date {
+                    return Err(MpesaError::validation(
+                        "end date",
+                        "is not later than start date",
+                    ));
+                }
+            }
+            (Err(_), _) => {
+                return Err(MpesaError::validation("start date", "has invalid value"));
+            }
+            (_, Err(_)) => {
+                return Err(MpesaError::validation("end date", "has invalid value"));
+            }
         }
 
-        if reciever_identifier_type.is_empty()
-            || reciever_identifier_type.replace(" ", "").trim().len() == 0
+        if business_short_code.is_empty() || business_short_code.replace(" ", "").trim().len() == 0
         {
-            return Err(String::from("reciever identifier type is empty"));
-        }
-
-        if _amount == 0 {
-            return Err(String::from("amount has invalid value"));
+            return Err(MpesaError::validation("business short code", "is empty"));
         }
 
-        if party_a.is_empty() || party_a.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("party a is empty"));
+        // business_short_code (5-6 digits) e.g. 123454
+        if business_short_code.len() == 5 || business_short_code.len() == 6 {
+        } else {
+            return Err(MpesaError::validation(
+                "business short code",
+                "has invalid value",
+            ));
         }
 
-        // party_a (5-6 digits) e.g. 123454
-        if party_a.to_string().len() == 5 || party_a.to_string().len() == 6 {
-        } else {
-            return Err(String::from("party a has invalid value"));
+        if transaction_type.is_empty() || transaction_type.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("transaction type", "is empty"));
         }
 
-        if party_b.is_empty() || party_b.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("party b is empty"));
+        if receiver_party_identifier_type.is_empty()
+            || receiver_party_identifier_type.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation(
+                "receiver party identifier type",
+                "is empty",
+            ));
         }
 
-        // party_b (5-6 digits) e.g. 123454
-        if party_b.to_string().len() == 5 || party_b.to_string().len() == 6 {
+        // Standing Order Customer Pay Bill (4 - paybill), Standing Order Customer Pay Marchant (2 - till)
+        if transaction_type.eq_ignore_ascii_case(STANDING_ORDER_PAY_BILL) {
+            if !receiver_party_identifier_type.eq("4") {
+                return Err(MpesaError::validation(
+                    "receiver party identifier type",
+                    "has invalid value",
+                ));
+            }
+        } else if transaction_type.eq_ignore_ascii_case(STANDING_ORDER_PAY_MERCHANT) {
+            if !receiver_party_identifier_type.eq("2") {
+                return Err(MpesaError::validation(
+                    "receiver party identifier type",
+                    "has invalid value",
+                ));
+            }
         } else {
-            return Err(String::from("party b has invalid value"));
+            return Err(MpesaError::validation(
+                "transaction type",
+                "has invalid value",
+            ));
         }
 
-        if account_reference.is_empty() || account_reference.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("account reference is empty"));
+        if _amount == 0 {
+            return Err(MpesaError::validation("amount", "has invalid value"));
         }
-        // account_reference has a max length of 13 characters
-        else if account_reference.trim().len() > 0 && account_reference.trim().len() <= 13 {
-            // account_reference is valid
+
+        // party_a (12 digits) e.g. 254708374149
+        if party_a.to_string().len() == 12 && party_a.to_string().starts_with("254") {
         } else {
-            return Err(String::from("account reference has invalid length"));
+            return Err(MpesaError::validation("party a", "has invalid value"));
         }
 
-        if _requester.is_empty() || _requester.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("_requester is empty"));
+        if callback_url.is_empty() || callback_url.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("callback url", "is empty"));
         }
 
-        if _remarks.is_empty() || _remarks.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("remarks is empty"));
+        if account_reference.is_empty() || account_reference.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("account reference", "is empty"));
         }
-        // _remarks has a max length of 100 characters
-        else if _remarks.trim().len() > 0 && _remarks.trim().len() <= 100 {
-            // _remarks is valid
-        } else {
-            return Err(String::from("remarks has invalid length"));
+        // account_reference has a max length of 12 characters
+        else if account_reference.trim().len() > 12 {
+            return Err(MpesaError::validation(
+                "account reference",
+                "has invalid length",
+            ));
         }
 
-        if queue_time_out_url.is_empty() || queue_time_out_url.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("queue_time_out url is empty"));
+        if transaction_desc.is_empty() || transaction_desc.replace(" ", "").trim().len() == 0 {
+            return Err(MpesaError::validation("transaction desc", "is empty"));
         }
-
-        if result_url.is_empty() || result_url.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("result url is empty"));
+        // transaction_desc has a max length of 13 characters
+        else if transaction_desc.trim().len() > 13 {
+            return Err(MpesaError::validation(
+                "transaction desc",
+                "has invalid length",
+            ));
         }
 
         Ok(Self {
             api_url,
-            _initiator,
-            security_credential,
-            command_id,
-            sender_identifier_type,
-            reciever_identifier_type,
+            standing_order_name,
+            start_date,
+            end_date,
+            business_short_code,
+            transaction_type,
+            receiver_party_identifier_type,
             _amount,
             party_a,
-            party_b,
+            callback_url,
             account_reference,
-            _requester,
-            _remarks,
-            queue_time_out_url,
-            result_url,
+            transaction_desc,
+            _frequency,
         })
     }
 
-    pub fn get_api_url(&self) -> String {
-        let api_url = &self.api_url;
-        api_url.to_string()
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
     }
 
-    pub fn get_initiator(&self) -> String {
-        let _initiator = &self._initiator;
-        _initiator.to_string()
+    pub fn get_standing_order_name(&self) -> String {
+        let standing_order_name = &self.standing_order_name;
+        standing_order_name.to_string()
     }
 
-    pub fn get_security_credential(&self) -> String {
-        let security_credential = &self.security_credential;
-        security_credential.to_string()
+    pub fn get_start_date(&self) -> String {
+        let start_date = &self.start_date;
+        start_date.to_string()
     }
 
-    pub fn get_command_id(&self) -> String {
-        let command_id = &self.command_id;
-        command_id.to_string()
+    pub fn get_end_date(&self) -> String {
+        let end_date = &self.end_date;
+        end_date.to_string()
     }
 
-    pub fn get_sender_identifier_type(&self) -> String {
-        let sender_identifier_type = &self.sender_identifier_type;
-        sender_identifier_type.to_string()
+    pub fn get_business_short_code(&self) -> String {
+        let business_short_code = &self.business_short_code;
+        business_short_code.to_string()
     }
 
-    pub fn get_reciever_identifier_type(&self) -> String {
-        let reciever_identifier_type = &self.reciever_identifier_type;
-        reciever_identifier_type.to_string()
+    pub fn get_transaction_type(&self) -> String {
+        let transaction_type = &self.transaction_type;
+        transaction_type.to_string()
+    }
+
+    pub fn get_receiver_party_identifier_type(&self) -> String {
+        let receiver_party_identifier_type = &self.receiver_party_identifier_type;
+        receiver_party_identifier_type.to_string()
     }
 
     pub fn get_amount(&self) -> u32 {
@@ -1012,14 +5218,14 @@ impl BusinessPayBillInputDetails {
         *_amount
     }
 
-    pub fn get_party_a(&self) -> String {
+    pub fn get_party_a(&self) -> u64 {
         let party_a = &self.party_a;
-        party_a.to_string()
+        *party_a
     }
 
-    pub fn get_party_b(&self) -> String {
-        let party_b = &self.party_b;
-        party_b.to_string()
+    pub fn get_callback_url(&self) -> String {
+        let callback_url = &self.callback_url;
+        callback_url.to_string()
     }
 
     pub fn get_account_reference(&self) -> String {
@@ -1027,155 +5233,295 @@ impl BusinessPayBillInputDetails {
         account_reference.to_string()
     }
 
-    pub fn get_requester(&self) -> String {
-        let _requester = &self._requester;
-        _requester.to_string()
+    pub fn get_transaction_desc(&self) -> String {
+        let transaction_desc = &self.transaction_desc;
+        transaction_desc.to_string()
     }
 
-    pub fn get_remarks(&self) -> String {
-        let _remarks = &self._remarks;
-        _remarks.to_string()
+    pub fn get_frequency(&self) -> StandingOrderFrequency {
+        self._frequency
     }
+}
 
-    pub fn get_queue_time_out_url(&self) -> String {
-        let queue_time_out_url = &self.queue_time_out_url;
-        queue_time_out_url.to_string()
+// This struct holds  Sim Swap check processing data
+#[derive(Debug)]
+pub struct SimSwapInputDetails {
+    api_url: Option<String>,
+    customer_number: u64,
+}
+
+impl SimSwapInputDetails {
+    pub fn new(api_url: Option<String>, customer_number: u64) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
+        }
+
+        if customer_number == 0 {
+            return Err(MpesaError::validation(
+                "customer number",
+                "has invalid value",
+            ));
+        }
+
+        // customer_number (12 digits) e.g. 254708374149
+        if customer_number.to_string().len() == 12 && customer_number.to_string().starts_with("254")
+        {
+        } else {
+            return Err(MpesaError::validation(
+                "customer number",
+                "has invalid value",
+            ));
+        }
+
+        Ok(Self {
+            api_url,
+            customer_number,
+        })
     }
 
-    pub fn get_result_url(&self) -> String {
-        let result_url = &self.result_url;
-        result_url.to_string()
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
+    }
+
+    pub fn get_customer_number(&self) -> u64 {
+        let customer_number = &self.customer_number;
+        *customer_number
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BusinessTransferCommand {
+    BusinessToBusinessTransfer,
+    MerchantToMerchantTransfer,
+    MerchantTransferFromMerchantToWorking,
+    MerchantServicesMMFAccountTransfer,
+    AgencyFloatAdvance,
+}
+
+impl BusinessTransferCommand {
+    pub fn get_command_id(&self) -> String {
+        let command_id = match self {
+            BusinessTransferCommand::BusinessToBusinessTransfer => "BusinessToBusinessTransfer",
+            BusinessTransferCommand::MerchantToMerchantTransfer => "MerchantToMerchantTransfer",
+            BusinessTransferCommand::MerchantTransferFromMerchantToWorking => {
+                "MerchantTransferFromMerchantToWorking"
+            }
+            BusinessTransferCommand::MerchantServicesMMFAccountTransfer => {
+                "MerchantServicesMMFAccountTransfer"
+            }
+            BusinessTransferCommand::AgencyFloatAdvance => "AgencyFloatAdvance",
+        };
+
+        command_id.to_string()
+    }
+
+    // The (sender, reciever) identifier types allowed for the command,
+    // 2 - till number, 4 - organisation short code
+    pub fn get_identifier_types(&self) -> (String, String) {
+        let (sender_identifier_type, reciever_identifier_type) = match self {
+            BusinessTransferCommand::BusinessToBusinessTransfer => ("4", "4"),
+            BusinessTransferCommand::MerchantToMerchantTransfer => ("2", "2"),
+            BusinessTransferCommand::MerchantTransferFromMerchantToWorking => ("2", "4"),
+            BusinessTransferCommand::MerchantServicesMMFAccountTransfer => ("2", "4"),
+            BusinessTransferCommand::AgencyFloatAdvance => ("4", "2"),
+        };
+
+        (
+            sender_identifier_type.to_string(),
+            reciever_identifier_type.to_string(),
+        )
     }
 }
 
+// This struct holds  Business Transfer (B2B command ids other than paybill/buy goods) processing data
 #[derive(Debug)]
-pub struct BusinessBuyGoodsInputDetails {
-    api_url: String,
+pub struct BusinessTransferInputDetails {
+    api_url: Option<String>,
     _initiator: String,
     security_credential: String,
-    command_id: String,
+    command_id: BusinessTransferCommand,
     sender_identifier_type: String,
     reciever_identifier_type: String,
     _amount: u32,
     party_a: String,
     party_b: String,
     account_reference: String,
-    _requester: String,
+    _requester: Option<String>,
     _remarks: String,
     queue_time_out_url: String,
     result_url: String,
 }
 
-impl BusinessBuyGoodsInputDetails {
+impl BusinessTransferInputDetails {
+    pub fn new_with_initiator_password(
+        api_url: Option<String>,
+        environment: &Environment,
+        _initiator: String,
+        initiator_password: String,
+        command_id: BusinessTransferCommand,
+        sender_identifier_type: String,
+        reciever_identifier_type: String,
+        _amount: u32,
+        party_a: String,
+        party_b: String,
+        account_reference: String,
+        _requester: Option<String>,
+        _remarks: String,
+        queue_time_out_url: String,
+        result_url: String,
+    ) -> Result<Self, MpesaError> {
+        let security_credential: String =
+            generate_security_credential_for_environment(&initiator_password, environment)?;
+
+        Self::new(
+            api_url,
+            _initiator,
+            security_credential,
+            command_id,
+            sender_identifier_type,
+            reciever_identifier_type,
+            _amount,
+            party_a,
+            party_b,
+            account_reference,
+            _requester,
+            _remarks,
+            queue_time_out_url,
+            result_url,
+        )
+    }
+
     pub fn new(
-        api_url: String,
+        api_url: Option<String>,
         _initiator: String,
         security_credential: String,
-        command_id: String,
+        command_id: BusinessTransferCommand,
         sender_identifier_type: String,
         reciever_identifier_type: String,
         _amount: u32,
         party_a: String,
         party_b: String,
         account_reference: String,
-        _requester: String,
+        _requester: Option<String>,
         _remarks: String,
         queue_time_out_url: String,
         result_url: String,
-    ) -> Result<Self, String> {
-        if api_url.is_empty() || api_url.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("api url is empty"));
+    ) -> Result<Self, MpesaError> {
+        if let Some(x) = &api_url {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("api url", "is empty"));
+            }
         }
 
         if _initiator.is_empty() || _initiator.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("initiator is empty"));
+            return Err(MpesaError::validation("initiator", "is empty"));
         }
 
         if security_credential.is_empty() || security_credential.replace(" ", "").trim().len() == 0
         {
-            return Err(String::from("security credential is empty"));
+            return Err(MpesaError::validation("security credential", "is empty"));
         }
 
-        if command_id.is_empty() || command_id.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("command id is empty"));
+        if sender_identifier_type.is_empty()
+            || sender_identifier_type.replace(" ", "").trim().len() == 0
+        {
+            return Err(MpesaError::validation("sender identifier type", "is empty"));
         }
 
-        // BusinessBuyGoods
-        if command_id
-            .to_lowercase()
-            .eq_ignore_ascii_case(&String::from("BusinessBuyGoods"))
+        if reciever_identifier_type.is_empty()
+            || reciever_identifier_type.replace(" ", "").trim().len() == 0
         {
-            // command id is valid
-        } else {
-            return Err(String::from("command has invalid value"));
+            return Err(MpesaError::validation(
+                "reciever identifier type",
+                "is empty",
+            ));
         }
 
-        if sender_identifier_type.is_empty()
-            || sender_identifier_type.replace(" ", "").trim().len() == 0
+        let (allowed_sender_identifier_type, allowed_reciever_identifier_type) =
+            command_id.get_identifier_types();
+
+        if !sender_identifier_type
+            .trim()
+            .eq(&allowed_sender_identifier_type)
         {
-            return Err(String::from("sender identifier type is empty"));
+            return Err(MpesaError::validation(
+                "sender identifier type",
+                "has invalid value",
+            ));
         }
 
-        if reciever_identifier_type.is_empty()
-            || reciever_identifier_type.replace(" ", "").trim().len() == 0
+        if !reciever_identifier_type
+            .trim()
+            .eq(&allowed_reciever_identifier_type)
         {
-            return Err(String::from("reciever identifier type is empty"));
+            return Err(MpesaError::validation(
+                "reciever identifier type",
+                "has invalid value",
+            ));
         }
 
         if _amount == 0 {
-            return Err(String::from("amount has invalid value"));
+            return Err(MpesaError::validation("amount", "has invalid value"));
         }
 
         if party_a.is_empty() || party_a.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("party a is empty"));
+            return Err(MpesaError::validation("party a", "is empty"));
         }
 
-        // party_a (5-6 digits) e.g. 123454
-        if party_a.to_string().len() == 5 || party_a.to_string().len() == 6 {
+        // party_a (5-7 digits) e.g. 123454
+        if party_a.len() >= 5 && party_a.len() <= 7 && party_a.chars().all(|c| c.is_ascii_digit()) {
         } else {
-            return Err(String::from("party a has invalid value"));
+            return Err(MpesaError::validation("party a", "has invalid value"));
         }
 
         if party_b.is_empty() || party_b.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("party b is empty"));
+            return Err(MpesaError::validation("party b", "is empty"));
         }
 
-        // party_b (5-6 digits) e.g. 123454
-        if party_b.to_string().len() == 5 || party_b.to_string().len() == 6 {
+        // party_b (5-7 digits) e.g. 123454
+        if party_b.len() >= 5 && party_b.len() <= 7 && party_b.chars().all(|c| c.is_ascii_digit()) {
         } else {
-            return Err(String::from("party b has invalid value"));
+            return Err(MpesaError::validation("party b", "has invalid value"));
         }
 
         if account_reference.is_empty() || account_reference.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("account reference is empty"));
+            return Err(MpesaError::validation("account reference", "is empty"));
         }
         // account_reference has a max length of 13 characters
         else if account_reference.trim().len() > 0 && account_reference.trim().len() <= 13 {
             // account_reference is valid
         } else {
-            return Err(String::from("account reference has invalid length"));
+            return Err(MpesaError::validation(
+                "account reference",
+                "has invalid length",
+            ));
         }
 
-        if _requester.is_empty() || _requester.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("_requester is empty"));
+        // _requester is optional, but must not be blank when provided
+        if let Some(x) = &_requester {
+            if x.is_empty() || x.replace(" ", "").trim().len() == 0 {
+                return Err(MpesaError::validation("_requester", "is empty"));
+            }
         }
 
         if _remarks.is_empty() || _remarks.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("remarks is empty"));
+            return Err(MpesaError::validation("remarks", "is empty"));
         }
         // _remarks has a max length of 100 characters
         else if _remarks.trim().len() > 0 && _remarks.trim().len() <= 100 {
             // _remarks is valid
         } else {
-            return Err(String::from("remarks has invalid length"));
+            return Err(MpesaError::validation("remarks", "has invalid length"));
         }
 
         if queue_time_out_url.is_empty() || queue_time_out_url.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("queue_time_out url is empty"));
+            return Err(MpesaError::validation("queue_time_out url", "is empty"));
         }
 
         if result_url.is_empty() || result_url.replace(" ", "").trim().len() == 0 {
-            return Err(String::from("result url is empty"));
+            return Err(MpesaError::validation("result url", "is empty"));
         }
 
         Ok(Self {
@@ -1183,8 +5529,8 @@ impl BusinessBuyGoodsInputDetails {
             _initiator,
             security_credential,
             command_id,
-            sender_identifier_type,
-            reciever_identifier_type,
+            sender_identifier_type: allowed_sender_identifier_type,
+            reciever_identifier_type: allowed_reciever_identifier_type,
             _amount,
             party_a,
             party_b,
@@ -1196,9 +5542,8 @@ impl BusinessBuyGoodsInputDetails {
         })
     }
 
-    pub fn get_api_url(&self) -> String {
-        let api_url = &self.api_url;
-        api_url.to_string()
+    pub fn get_api_url(&self) -> Option<String> {
+        self.api_url.clone()
     }
 
     pub fn get_initiator(&self) -> String {
@@ -1212,8 +5557,7 @@ impl BusinessBuyGoodsInputDetails {
     }
 
     pub fn get_command_id(&self) -> String {
-        let command_id = &self.command_id;
-        command_id.to_string()
+        self.command_id.get_command_id()
     }
 
     pub fn get_sender_identifier_type(&self) -> String {
@@ -1246,9 +5590,8 @@ impl BusinessBuyGoodsInputDetails {
         account_reference.to_string()
     }
 
-    pub fn get_requester(&self) -> String {
-        let _requester = &self._requester;
-        _requester.to_string()
+    pub fn get_requester(&self) -> Option<String> {
+        self._requester.clone()
     }
 
     pub fn get_remarks(&self) -> String {
@@ -1279,6 +5622,23 @@ pub struct B2CResultParametersOutputDetails {
     pub B2CWorkingAccountAvailableFunds: f32,
 }
 
+#[derive(Debug)]
+pub struct B2PochiResultParametersOutputDetails {
+    // The parameters shared with b2c results
+    pub B2CResultParameters: B2CResultParametersOutputDetails,
+    // Any other parameters (key, value) sent in the b2pochi result
+    pub AdditionalParameters: Vec<(String, String)>,
+}
+
+#[derive(Debug)]
+pub struct StandingOrderCallbackOutputDetails {
+    pub ResponseCode: String,
+    pub ResponseDescription: String,
+    pub TransactionID: String,
+    pub Status: String,
+    pub Msisdn: String,
+}
+
 #[derive(Debug)]
 pub struct C2BPaymentResultParametersOutputDetails {
     pub Amount: f32,
@@ -1334,3 +5694,58 @@ pub struct BusinessBuyGoodsTimeoutParametersOutputDetails {
     pub BOCompletedTime: String,
     pub QueueTimeoutURL: String,
 }
+
+#[derive(Debug)]
+pub struct TransactionStatusResultParametersOutputDetails {
+    pub DebitPartyName: String,
+    pub CreditPartyName: String,
+    pub OriginatorConversationID: String,
+    pub InitiatedTime: String,
+    pub DebitAccountType: String,
+    pub DebitPartyCharges: String,
+    pub TransactionReason: String,
+    pub ReasonType: String,
+    pub TransactionStatus: String,
+    pub FinalisedTime: String,
+    pub Amount: f32,
+    pub ConversationID: String,
+    pub ReceiptNo: String,
+}
+
+// One entry of the AccountBalance result parameter e.g.
+// "Working Account|KES|700000.00|700000.00|0.00|0.00"
+#[derive(Debug)]
+pub struct AccountBalanceOutputDetails {
+    pub AccountName: String,
+    pub Currency: String,
+    pub CurrentBalance: f64,
+    pub AvailableBalance: f64,
+    pub ReservedAmount: f64,
+    pub UnclearedBalance: f64,
+}
+
+#[derive(Debug)]
+pub struct AccountBalanceResultParametersOutputDetails {
+    pub AccountBalance: Vec<AccountBalanceOutputDetails>,
+    pub BOCompletedTime: String,
+}
+
+#[derive(Debug)]
+pub struct ReversalResultParametersOutputDetails {
+    pub DebitAccountBalance: String,
+    pub Amount: f32,
+    pub TransCompletedTime: String,
+    pub OriginalTransactionID: String,
+    pub Charge: f32,
+    pub CreditPartyPublicName: String,
+    pub DebitPartyPublicName: String,
+}
+
+#[derive(Debug)]
+pub struct BusinessExpressCheckoutResultOutputDetails {
+    pub Amount: f32,
+    pub TransactionId: String,
+    pub ConversationID: String,
+    pub RequestId: String,
+    pub Status: String,
+}
@@ -0,0 +1,186 @@
+use reqwest::StatusCode;
+
+use crate::{
+    error::error::MpesaError,
+    models::models::{
+        PullTransactionsErrorResponseData, PullTransactionsQueryInputDetails,
+        PullTransactionsQueryResponseData, PullTransactionsRegisterInputDetails,
+        PullTransactionsRegisterResponseData,
+    },
+    util::util::{
+        build_headers, build_pull_transactions_query_data, build_pull_transactions_register_data,
+    },
+};
+
+// register a short code for pull transactions
+pub async fn register(
+    client: &reqwest::Client,
+    pull_transactions_register_details: &PullTransactionsRegisterInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<PullTransactionsRegisterResponseData>,
+        Option<PullTransactionsErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let short_code: String = pull_transactions_register_details.get_short_code();
+    let request_type: String = pull_transactions_register_details.get_request_type();
+    let nominated_number: String = pull_transactions_register_details.get_nominated_number();
+    let callback_url: String = pull_transactions_register_details.get_callback_url();
+
+    let pull_transactions_register_data = build_pull_transactions_register_data(
+        short_code,
+        request_type,
+        nominated_number,
+        callback_url,
+    );
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&pull_transactions_register_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response
+                    .json::<PullTransactionsRegisterResponseData>()
+                    .await
+                {
+                    Ok(pull_transactions_register_response_data) => {
+                        // Handle success case
+                        let pull_transactions_register_error_response_data = None;
+                        let my_output = (
+                            Some(pull_transactions_register_response_data),
+                            pull_transactions_register_error_response_data,
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<PullTransactionsErrorResponseData>(&body) {
+                    Ok(pull_transactions_register_error_response_data) => {
+                        // Handle success case
+                        let pull_transactions_register_response_data = None;
+                        let my_output = (
+                            pull_transactions_register_response_data,
+                            Some(pull_transactions_register_error_response_data),
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
+
+// retrieve transactions for a short code within a given time window
+pub async fn query(
+    client: &reqwest::Client,
+    pull_transactions_query_details: &PullTransactionsQueryInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<PullTransactionsQueryResponseData>,
+        Option<PullTransactionsErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let short_code: String = pull_transactions_query_details.get_short_code();
+    let start_date: String = pull_transactions_query_details.get_start_date();
+    let end_date: String = pull_transactions_query_details.get_end_date();
+    let offset_value: u32 = pull_transactions_query_details.get_offset_value();
+
+    let pull_transactions_query_data =
+        build_pull_transactions_query_data(short_code, start_date, end_date, offset_value);
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&pull_transactions_query_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response.json::<PullTransactionsQueryResponseData>().await {
+                    Ok(pull_transactions_query_response_data) => {
+                        // Handle success case
+                        let pull_transactions_query_error_response_data = None;
+                        let my_output = (
+                            Some(pull_transactions_query_response_data),
+                            pull_transactions_query_error_response_data,
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<PullTransactionsErrorResponseData>(&body) {
+                    Ok(pull_transactions_query_error_response_data) => {
+                        // Handle success case
+                        let pull_transactions_query_response_data = None;
+                        let my_output = (
+                            pull_transactions_query_response_data,
+                            Some(pull_transactions_query_error_response_data),
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
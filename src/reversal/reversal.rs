@@ -0,0 +1,103 @@
+use reqwest::StatusCode;
+
+use crate::{
+    error::error::MpesaError,
+    models::models::{ReversalErrorResponseData, ReversalInputDetails, ReversalResponseData},
+    util::util::{build_headers, build_reversal_data},
+};
+
+pub async fn reversal(
+    client: &reqwest::Client,
+    reversal_details: &ReversalInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<ReversalResponseData>,
+        Option<ReversalErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let _initiator: String = reversal_details.get_initiator();
+    let security_credential: String = reversal_details.get_security_credential();
+    let command_id: String = reversal_details.get_command_id();
+    let transaction_id: String = reversal_details.get_transaction_id();
+    let _amount: u32 = reversal_details.get_amount();
+    let receiver_party: String = reversal_details.get_receiver_party();
+    let reciever_identifier_type: String = reversal_details.get_reciever_identifier_type();
+    let result_url: String = reversal_details.get_result_url();
+    let queue_time_out_url: String = reversal_details.get_queue_time_out_url();
+    let _remarks: String = reversal_details.get_remarks();
+    let _occasion: String = reversal_details.get_occasion();
+
+    let reversal_data = build_reversal_data(
+        _initiator,
+        security_credential,
+        command_id,
+        transaction_id,
+        _amount,
+        receiver_party,
+        reciever_identifier_type,
+        result_url,
+        queue_time_out_url,
+        _remarks,
+        _occasion,
+    );
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&reversal_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response.json::<ReversalResponseData>().await {
+                    Ok(reversal_response_data) => {
+                        // Handle success case
+                        let reversal_error_response_data = None;
+                        let my_output =
+                            (Some(reversal_response_data), reversal_error_response_data);
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<ReversalErrorResponseData>(&body) {
+                    Ok(reversal_error_response_data) => {
+                        // Handle success case
+                        let reversal_response_data = None;
+                        let my_output =
+                            (reversal_response_data, Some(reversal_error_response_data));
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
@@ -0,0 +1,108 @@
+use crate::{environment::environment::Environment, error::error::MpesaError};
+use base64::{engine::general_purpose, Engine as _};
+use rsa::{pkcs8::DecodePublicKey, Pkcs1v15Encrypt, RsaPublicKey};
+use x509_cert::{
+    der::{Decode, DecodePem, Encode},
+    Certificate,
+};
+
+// M-Pesa public certificates (SandboxCertificate.cer and ProductionCertificate.cer from the
+// daraja portal) bundled with the sdk
+pub(crate) const SANDBOX_CERTIFICATE: &[u8] =
+    include_bytes!("certificates/sandbox_certificate.cer");
+pub(crate) const PRODUCTION_CERTIFICATE: &[u8] =
+    include_bytes!("certificates/production_certificate.cer");
+
+// Returns the bundled certificate of the environment. There is no bundled certificate for a
+// custom environment, generate_security_credential takes the certificate of the stand-in instead.
+pub fn get_certificate(
+    environment: &Environment,
+) -> std::result::Result<&'static [u8], MpesaError> {
+    match environment {
+        Environment::Sandbox => Ok(SANDBOX_CERTIFICATE),
+        Environment::Production => Ok(PRODUCTION_CERTIFICATE),
+        Environment::Custom(_) => Err(MpesaError::validation(
+            "certificate",
+            "is not bundled for a custom environment",
+        )),
+    }
+}
+
+// Generates the SecurityCredential with the bundled certificate of the environment
+pub fn generate_security_credential_for_environment(
+    initiator_password: &str,
+    environment: &Environment,
+) -> std::result::Result<String, MpesaError> {
+    let certificate = get_certificate(environment)?;
+
+    generate_security_credential(initiator_password, certificate)
+}
+
+// Generates the SecurityCredential used by b2c, b2b, reversal, transaction status,
+// account balance and tax remittance requests: the initiator password encrypted
+// (RSA PKCS#1 v1.5) with the public key of the M-Pesa certificate, base64 encoded.
+// certificate is the content of the certificate (.cer) downloaded from the daraja portal,
+// either PEM or DER encoded; sandbox and production use different certificates.
+pub fn generate_security_credential(
+    initiator_password: &str,
+    certificate: &[u8],
+) -> std::result::Result<String, MpesaError> {
+    if initiator_password.is_empty() || initiator_password.replace(" ", "").trim().len() == 0 {
+        return Err(MpesaError::validation("initiator password", "is empty"));
+    }
+
+    if certificate.is_empty() {
+        return Err(MpesaError::validation("certificate", "is empty"));
+    }
+
+    let public_key = get_public_key(certificate)?;
+
+    let mut rng = rand::thread_rng();
+
+    match public_key.encrypt(&mut rng, Pkcs1v15Encrypt, initiator_password.as_bytes()) {
+        Ok(encrypted_password) => Ok(general_purpose::STANDARD.encode(encrypted_password)),
+        Err(_err) => Err(MpesaError::validation(
+            "initiator password",
+            "could not be encrypted",
+        )),
+    }
+}
+
+fn get_public_key(certificate: &[u8]) -> std::result::Result<RsaPublicKey, MpesaError> {
+    let is_pem = certificate
+        .iter()
+        .skip_while(|x| x.is_ascii_whitespace())
+        .take(10)
+        .eq(b"-----BEGIN".iter());
+
+    let certificate = if is_pem {
+        Certificate::from_pem(certificate)
+    } else {
+        Certificate::from_der(certificate)
+    };
+
+    let certificate = match certificate {
+        Ok(x) => x,
+        Err(_err) => {
+            return Err(MpesaError::validation("certificate", "has invalid value"));
+        }
+    };
+
+    let public_key_info = match certificate.tbs_certificate.subject_public_key_info.to_der() {
+        Ok(x) => x,
+        Err(_err) => {
+            return Err(MpesaError::validation(
+                "certificate",
+                "has invalid public key",
+            ));
+        }
+    };
+
+    match RsaPublicKey::from_public_key_der(&public_key_info) {
+        Ok(x) => Ok(x),
+        Err(_err) => Err(MpesaError::validation(
+            "certificate",
+            "has invalid public key",
+        )),
+    }
+}
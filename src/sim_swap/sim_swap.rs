@@ -0,0 +1,82 @@
+use reqwest::StatusCode;
+
+use crate::{
+    error::error::MpesaError,
+    models::models::{SimSwapErrorResponseData, SimSwapInputDetails, SimSwapResponseData},
+    util::util::{build_headers, build_sim_swap_data},
+};
+
+// query the last sim swap date of a customer number
+pub async fn check_sim_swap(
+    client: &reqwest::Client,
+    sim_swap_details: &SimSwapInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<SimSwapResponseData>,
+        Option<SimSwapErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let customer_number: u64 = sim_swap_details.get_customer_number();
+
+    let sim_swap_data = build_sim_swap_data(customer_number);
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&sim_swap_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response.json::<SimSwapResponseData>().await {
+                    Ok(sim_swap_response_data) => {
+                        // Handle success case
+                        let sim_swap_error_response_data = None;
+                        let my_output =
+                            (Some(sim_swap_response_data), sim_swap_error_response_data);
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<SimSwapErrorResponseData>(&body) {
+                    Ok(sim_swap_error_response_data) => {
+                        // Handle success case
+                        let sim_swap_response_data = None;
+                        let my_output =
+                            (sim_swap_response_data, Some(sim_swap_error_response_data));
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
@@ -0,0 +1,113 @@
+use reqwest::StatusCode;
+
+use crate::{
+    error::error::MpesaError,
+    models::models::{
+        StandingOrderErrorResponseData, StandingOrderInputDetails, StandingOrderResponseData,
+    },
+    util::util::{build_headers, build_standing_order_data},
+};
+
+// create a standing order (Lipa na M-Pesa Ratiba)
+pub async fn create_standing_order(
+    client: &reqwest::Client,
+    standing_order_details: &StandingOrderInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<StandingOrderResponseData>,
+        Option<StandingOrderErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let standing_order_name: String = standing_order_details.get_standing_order_name();
+    let start_date: String = standing_order_details.get_start_date();
+    let end_date: String = standing_order_details.get_end_date();
+    let business_short_code: String = standing_order_details.get_business_short_code();
+    let transaction_type: String = standing_order_details.get_transaction_type();
+    let receiver_party_identifier_type: String =
+        standing_order_details.get_receiver_party_identifier_type();
+    let _amount: u32 = standing_order_details.get_amount();
+    let party_a: u64 = standing_order_details.get_party_a();
+    let callback_url: String = standing_order_details.get_callback_url();
+    let account_reference: String = standing_order_details.get_account_reference();
+    let transaction_desc: String = standing_order_details.get_transaction_desc();
+    let _frequency: String = standing_order_details.get_frequency().get_code();
+
+    let standing_order_data = build_standing_order_data(
+        standing_order_name,
+        start_date,
+        end_date,
+        business_short_code,
+        transaction_type,
+        receiver_party_identifier_type,
+        _amount,
+        party_a,
+        callback_url,
+        account_reference,
+        transaction_desc,
+        _frequency,
+    );
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&standing_order_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response.json::<StandingOrderResponseData>().await {
+                    Ok(standing_order_response_data) => {
+                        // Handle success case
+                        let standing_order_error_response_data = None;
+                        let my_output = (
+                            Some(standing_order_response_data),
+                            standing_order_error_response_data,
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<StandingOrderErrorResponseData>(&body) {
+                    Ok(standing_order_error_response_data) => {
+                        // Handle success case
+                        let standing_order_response_data = None;
+                        let my_output = (
+                            standing_order_response_data,
+                            Some(standing_order_error_response_data),
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
@@ -0,0 +1,105 @@
+use reqwest::StatusCode;
+
+use crate::{
+    error::error::MpesaError,
+    models::models::{RemitTaxErrorResponseData, RemitTaxInputDetails, RemitTaxResponseData},
+    util::util::{build_headers, build_remit_tax_data},
+};
+
+pub async fn remit_tax(
+    client: &reqwest::Client,
+    remit_tax_details: &RemitTaxInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<RemitTaxResponseData>,
+        Option<RemitTaxErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let _initiator: String = remit_tax_details.get_initiator();
+    let security_credential: String = remit_tax_details.get_security_credential();
+    let command_id: String = remit_tax_details.get_command_id();
+    let sender_identifier_type: String = remit_tax_details.get_sender_identifier_type();
+    let reciever_identifier_type: String = remit_tax_details.get_reciever_identifier_type();
+    let _amount: u32 = remit_tax_details.get_amount();
+    let party_a: String = remit_tax_details.get_party_a();
+    let party_b: String = remit_tax_details.get_party_b();
+    let account_reference: String = remit_tax_details.get_account_reference();
+    let _remarks: String = remit_tax_details.get_remarks();
+    let queue_time_out_url: String = remit_tax_details.get_queue_time_out_url();
+    let result_url: String = remit_tax_details.get_result_url();
+
+    let remit_tax_data = build_remit_tax_data(
+        _initiator,
+        security_credential,
+        command_id,
+        sender_identifier_type,
+        reciever_identifier_type,
+        _amount,
+        party_a,
+        party_b,
+        account_reference,
+        _remarks,
+        queue_time_out_url,
+        result_url,
+    );
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&remit_tax_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response.json::<RemitTaxResponseData>().await {
+                    Ok(remit_tax_response_data) => {
+                        // Handle success case
+                        let remit_tax_error_response_data = None;
+                        let my_output =
+                            (Some(remit_tax_response_data), remit_tax_error_response_data);
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<RemitTaxErrorResponseData>(&body) {
+                    Ok(remit_tax_error_response_data) => {
+                        // Handle success case
+                        let remit_tax_response_data = None;
+                        let my_output =
+                            (remit_tax_response_data, Some(remit_tax_error_response_data));
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
@@ -0,0 +1,111 @@
+use reqwest::StatusCode;
+
+use crate::{
+    error::error::MpesaError,
+    models::models::{
+        TransactionStatusErrorResponseData, TransactionStatusInputDetails,
+        TransactionStatusResponseData,
+    },
+    util::util::{build_headers, build_transaction_status_data},
+};
+
+pub async fn transaction_status(
+    client: &reqwest::Client,
+    transaction_status_details: &TransactionStatusInputDetails,
+    api_url: String,
+    access_token: String,
+) -> std::result::Result<
+    (
+        Option<TransactionStatusResponseData>,
+        Option<TransactionStatusErrorResponseData>,
+    ),
+    MpesaError,
+> {
+    let _initiator: String = transaction_status_details.get_initiator();
+    let security_credential: String = transaction_status_details.get_security_credential();
+    let command_id: String = transaction_status_details.get_command_id();
+    let transaction_id: String = transaction_status_details.get_transaction_id();
+    let original_conversation_id: String =
+        transaction_status_details.get_original_conversation_id();
+    let party_a: String = transaction_status_details.get_party_a();
+    let identifier_type: String = transaction_status_details.get_identifier_type();
+    let result_url: String = transaction_status_details.get_result_url();
+    let queue_time_out_url: String = transaction_status_details.get_queue_time_out_url();
+    let _remarks: String = transaction_status_details.get_remarks();
+    let _occasion: String = transaction_status_details.get_occasion();
+
+    let transaction_status_data = build_transaction_status_data(
+        _initiator,
+        security_credential,
+        command_id,
+        transaction_id,
+        original_conversation_id,
+        party_a,
+        identifier_type,
+        result_url,
+        queue_time_out_url,
+        _remarks,
+        _occasion,
+    );
+
+    let res = client
+        .post(api_url)
+        .headers(build_headers(access_token))
+        .json(&transaction_status_data)
+        .send()
+        .await;
+
+    match res {
+        Err(_err) => {
+            return Err(MpesaError::Transport(_err.to_string()));
+        }
+        Ok(response) => match response.status() {
+            StatusCode::OK => {
+                match response.json::<TransactionStatusResponseData>().await {
+                    Ok(transaction_status_response_data) => {
+                        // Handle success case
+                        let transaction_status_error_response_data = None;
+                        let my_output = (
+                            Some(transaction_status_response_data),
+                            transaction_status_error_response_data,
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::JsonDecode(_err.to_string()));
+                    }
+                }
+            }
+            StatusCode::UNAUTHORIZED => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                return Err(MpesaError::HttpStatus { status: 401, body });
+            }
+            s => {
+                let body: String = response.text().await.unwrap_or_default();
+
+                match serde_json::from_str::<TransactionStatusErrorResponseData>(&body) {
+                    Ok(transaction_status_error_response_data) => {
+                        // Handle success case
+                        let transaction_status_response_data = None;
+                        let my_output = (
+                            transaction_status_response_data,
+                            Some(transaction_status_error_response_data),
+                        );
+
+                        return Ok(my_output);
+                    }
+                    Err(_err) => {
+                        // Handle error case
+                        return Err(MpesaError::HttpStatus {
+                            status: s.as_u16(),
+                            body,
+                        });
+                    }
+                }
+            }
+        },
+    };
+}
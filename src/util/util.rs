@@ -1,14 +1,59 @@
+use base64::{engine::general_purpose, Engine as _};
 use reqwest::header::HeaderMap;
 use reqwest::header::{ACCEPT, CONTENT_TYPE};
 
+use crate::error::error::MpesaError;
 use crate::models::models::{
+    AccountBalanceData, AccountBalanceOutputDetails, B2CAccountTopUpData, B2PochiData,
+    BillManagerCancelInvoiceData, BillManagerInvoiceData, BillManagerInvoiceDetails,
+    BillManagerInvoiceItemData, BillManagerOptInData, BillManagerReconciliationData,
     BusinessBuyGoodsData, BusinessBuyGoodsErrorResponseData, BusinessBuyGoodsResponseData,
-    BusinessPayBillData, BusinessPayBillErrorResponseData, BusinessPayBillResponseData,
-    BusinessToCustomerData, BusinessToCustomerErrorResponseData, BusinessToCustomerResponseData,
+    BusinessExpressCheckoutData, BusinessPayBillData, BusinessPayBillErrorResponseData,
+    BusinessPayBillResponseData, BusinessToCustomerData, BusinessToCustomerErrorResponseData,
+    BusinessToCustomerResponseData, BusinessTransferData, CustomerIdentityData,
     CustomerToBusinessPaymentData, CustomerToBusinessPaymentErrorResponseData,
-    CustomerToBusinessPaymentResponseData, RegisterUrlData, RegisterUrlResponseData,
+    CustomerToBusinessPaymentQueryData, CustomerToBusinessPaymentResponseData,
+    CustomerToBusinessSimulateData, DynamicQrData, PullTransactionsQueryData,
+    PullTransactionsRegisterData, RegisterUrlData, RegisterUrlResponseData, RemitTaxData,
+    ReversalData, SimSwapData, StandingOrderData, TransactionStatusData,
 };
 
+// Daraja works in Africa/Nairobi time (UTC+3, no daylight saving) regardless of the server
+// timezone
+pub fn get_nairobi_time() -> chrono::NaiveDateTime {
+    chrono::Utc::now().naive_utc() + chrono::Duration::hours(3)
+}
+
+// M-Pesa expects the STK push time stamp (YYYYMMDDHHmmss) in Africa/Nairobi time
+pub fn get_stk_time_stamp() -> String {
+    get_nairobi_time().format("%Y%m%d%H%M%S").to_string()
+}
+
+// _password = base64(Shortcode+Passkey+Timestamp)
+pub fn build_stk_password(business_short_code: &str, pass_key: &str, time_stamp: &str) -> String {
+    let mut _password: String = business_short_code.to_string();
+    _password.push_str(pass_key);
+    _password.push_str(time_stamp);
+
+    general_purpose::STANDARD.encode(_password)
+}
+
+pub fn is_valid_stk_password(_password: &str, business_short_code: &str, time_stamp: &str) -> bool {
+    let decoded_password = match general_purpose::STANDARD.decode(_password.trim()) {
+        Ok(x) => x,
+        Err(_) => return false,
+    };
+
+    match String::from_utf8(decoded_password) {
+        Ok(x) => {
+            x.len() > business_short_code.len() + time_stamp.len()
+                && x.starts_with(business_short_code)
+                && x.ends_with(time_stamp)
+        }
+        Err(_) => false,
+    }
+}
+
 pub fn build_business_to_customer_response_data(
     originator_conversation_id: Option<String>,
     conversation_id: Option<String>,
@@ -132,11 +177,13 @@ pub fn build_register_url_data(
 pub fn build_register_url_response_data(
     originator_conversation_id: Option<String>,
     conversation_id: Option<String>,
+    response_code: Option<String>,
     response_description: Option<String>,
 ) -> RegisterUrlResponseData {
     RegisterUrlResponseData {
         OriginatorCoversationID: originator_conversation_id,
         ConversationID: conversation_id,
+        ResponseCode: response_code,
         ResponseDescription: response_description,
     }
 }
@@ -169,6 +216,34 @@ pub fn build_business_to_customer_data(
     }
 }
 
+pub fn build_b2_pochi_data(
+    originator_conversation_id: String,
+    initiator_name: String,
+    security_credential: String,
+    command_id: String,
+    amount: u32,
+    party_a: u32,
+    party_b: String,
+    _remarks: String,
+    queue_time_out_url: String,
+    result_url: String,
+    _occasion: String,
+) -> B2PochiData {
+    B2PochiData {
+        OriginatorConversationID: originator_conversation_id,
+        InitiatorName: initiator_name,
+        SecurityCredential: security_credential,
+        CommandID: command_id,
+        Amount: amount,
+        PartyA: party_a,
+        PartyB: party_b,
+        Remarks: _remarks,
+        QueueTimeOutURL: queue_time_out_url,
+        ResultURL: result_url,
+        Occasion: _occasion,
+    }
+}
+
 pub fn build_customer_to_business_data(
     business_short_code: String,
     _password: String,
@@ -197,6 +272,36 @@ pub fn build_customer_to_business_data(
     }
 }
 
+pub fn build_customer_to_business_payment_query_data(
+    business_short_code: String,
+    _password: String,
+    time_stamp: String,
+    checkout_request_id: String,
+) -> CustomerToBusinessPaymentQueryData {
+    CustomerToBusinessPaymentQueryData {
+        BusinessShortCode: business_short_code,
+        Password: _password,
+        Timestamp: time_stamp,
+        CheckoutRequestID: checkout_request_id,
+    }
+}
+
+pub fn build_customer_to_business_simulate_data(
+    short_code: String,
+    command_id: String,
+    _amount: u32,
+    _msisdn: u64,
+    bill_ref_number: String,
+) -> CustomerToBusinessSimulateData {
+    CustomerToBusinessSimulateData {
+        ShortCode: short_code,
+        CommandID: command_id,
+        Amount: _amount,
+        Msisdn: _msisdn,
+        BillRefNumber: bill_ref_number,
+    }
+}
+
 pub fn build_business_paybill_data(
     _initiator: String,
     security_credential: String,
@@ -261,6 +366,421 @@ pub fn build_business_buy_goods_data(
     }
 }
 
+pub fn build_business_express_checkout_data(
+    primary_short_code: String,
+    receiver_short_code: String,
+    _amount: u32,
+    payment_ref: String,
+    callback_url: String,
+    partner_name: String,
+    request_ref_id: String,
+) -> BusinessExpressCheckoutData {
+    BusinessExpressCheckoutData {
+        primaryShortCode: primary_short_code,
+        receiverShortCode: receiver_short_code,
+        amount: _amount.to_string(),
+        paymentRef: payment_ref,
+        callbackUrl: callback_url,
+        partnerName: partner_name,
+        RequestRefID: request_ref_id,
+    }
+}
+
+pub fn build_remit_tax_data(
+    _initiator: String,
+    security_credential: String,
+    command_id: String,
+    sender_identifier_type: String,
+    reciever_identifier_type: String,
+    _amount: u32,
+    party_a: String,
+    party_b: String,
+    account_reference: String,
+    _remarks: String,
+    queue_time_out_url: String,
+    result_url: String,
+) -> RemitTaxData {
+    RemitTaxData {
+        Initiator: _initiator,
+        SecurityCredential: security_credential,
+        CommandID: command_id,
+        SenderIdentifierType: sender_identifier_type,
+        RecieverIdentifierType: reciever_identifier_type,
+        Amount: _amount,
+        PartyA: party_a,
+        PartyB: party_b,
+        AccountReference: account_reference,
+        Remarks: _remarks,
+        QueueTimeOutURL: queue_time_out_url,
+        ResultURL: result_url,
+    }
+}
+
+pub fn build_bill_manager_opt_in_data(
+    short_code: String,
+    _email: String,
+    official_contact: String,
+    send_reminders: String,
+    _logo: String,
+    callback_url: String,
+) -> BillManagerOptInData {
+    BillManagerOptInData {
+        shortcode: short_code,
+        email: _email,
+        officialContact: official_contact,
+        sendReminders: send_reminders,
+        logo: _logo,
+        callbackurl: callback_url,
+    }
+}
+
+pub fn build_bill_manager_invoice_data(
+    _invoice: &BillManagerInvoiceDetails,
+) -> BillManagerInvoiceData {
+    let invoice_items: Vec<BillManagerInvoiceItemData> = _invoice
+        .get_invoice_items()
+        .iter()
+        .map(|invoice_item| BillManagerInvoiceItemData {
+            itemName: invoice_item.get_item_name(),
+            amount: invoice_item.get_amount().to_string(),
+        })
+        .collect();
+
+    BillManagerInvoiceData {
+        externalReference: _invoice.get_external_reference(),
+        billedFullName: _invoice.get_billed_full_name(),
+        billedPhoneNumber: _invoice.get_billed_phone_number(),
+        billedPeriod: _invoice.get_billed_period(),
+        invoiceName: _invoice.get_invoice_name(),
+        dueDate: _invoice.get_due_date(),
+        accountReference: _invoice.get_account_reference(),
+        amount: _invoice.get_amount().to_string(),
+        invoiceItems: invoice_items,
+    }
+}
+
+pub fn build_bill_manager_cancel_invoice_data(
+    external_reference: String,
+) -> BillManagerCancelInvoiceData {
+    BillManagerCancelInvoiceData {
+        externalReference: external_reference,
+    }
+}
+
+pub fn build_bill_manager_reconciliation_data(
+    payment_date: String,
+    paid_amount: u32,
+    account_reference: String,
+    transaction_id: String,
+    phone_number: String,
+    full_name: String,
+    invoice_name: String,
+    external_reference: String,
+) -> BillManagerReconciliationData {
+    BillManagerReconciliationData {
+        paymentDate: payment_date,
+        paidAmount: paid_amount.to_string(),
+        accountReference: account_reference,
+        transactionId: transaction_id,
+        phoneNumber: phone_number,
+        fullName: full_name,
+        invoiceName: invoice_name,
+        externalReference: external_reference,
+    }
+}
+
+pub fn build_pull_transactions_register_data(
+    short_code: String,
+    request_type: String,
+    nominated_number: String,
+    callback_url: String,
+) -> PullTransactionsRegisterData {
+    PullTransactionsRegisterData {
+        ShortCode: short_code,
+        RequestType: request_type,
+        NominatedNumber: nominated_number,
+        CallBackURL: callback_url,
+    }
+}
+
+pub fn build_pull_transactions_query_data(
+    short_code: String,
+    start_date: String,
+    end_date: String,
+    offset_value: u32,
+) -> PullTransactionsQueryData {
+    PullTransactionsQueryData {
+        ShortCode: short_code,
+        StartDate: start_date,
+        EndDate: end_date,
+        OffSetValue: offset_value.to_string(),
+    }
+}
+
+pub fn build_b2c_account_top_up_data(
+    _initiator: String,
+    security_credential: String,
+    command_id: String,
+    sender_identifier_type: String,
+    reciever_identifier_type: String,
+    _amount: u32,
+    party_a: String,
+    party_b: String,
+    account_reference: String,
+    _requester: Option<String>,
+    _remarks: String,
+    queue_time_out_url: String,
+    result_url: String,
+) -> B2CAccountTopUpData {
+    B2CAccountTopUpData {
+        Initiator: _initiator,
+        SecurityCredential: security_credential,
+        CommandID: command_id,
+        SenderIdentifierType: sender_identifier_type,
+        RecieverIdentifierType: reciever_identifier_type,
+        Amount: _amount,
+        PartyA: party_a,
+        PartyB: party_b,
+        AccountReference: account_reference,
+        Requester: _requester,
+        Remarks: _remarks,
+        QueueTimeOutURL: queue_time_out_url,
+        ResultURL: result_url,
+    }
+}
+
+pub fn build_standing_order_data(
+    standing_order_name: String,
+    start_date: String,
+    end_date: String,
+    business_short_code: String,
+    transaction_type: String,
+    receiver_party_identifier_type: String,
+    _amount: u32,
+    party_a: u64,
+    callback_url: String,
+    account_reference: String,
+    transaction_desc: String,
+    _frequency: String,
+) -> StandingOrderData {
+    StandingOrderData {
+        StandingOrderName: standing_order_name,
+        StartDate: start_date,
+        EndDate: end_date,
+        BusinessShortCode: business_short_code,
+        TransactionType: transaction_type,
+        ReceiverPartyIdentifierType: receiver_party_identifier_type,
+        Amount: _amount.to_string(),
+        PartyA: party_a.to_string(),
+        CallBackURL: callback_url,
+        AccountReference: account_reference,
+        TransactionDesc: transaction_desc,
+        Frequency: _frequency,
+    }
+}
+
+pub fn build_sim_swap_data(customer_number: u64) -> SimSwapData {
+    SimSwapData {
+        customerNumber: customer_number.to_string(),
+    }
+}
+
+pub fn build_customer_identity_data(
+    _msisdn: u64,
+    id_type: String,
+    id_number: String,
+) -> CustomerIdentityData {
+    CustomerIdentityData {
+        MSISDN: _msisdn.to_string(),
+        IDType: id_type,
+        IDNumber: id_number,
+    }
+}
+
+pub fn build_business_transfer_data(
+    _initiator: String,
+    security_credential: String,
+    command_id: String,
+    sender_identifier_type: String,
+    reciever_identifier_type: String,
+    _amount: u32,
+    party_a: String,
+    party_b: String,
+    account_reference: String,
+    _requester: Option<String>,
+    _remarks: String,
+    queue_time_out_url: String,
+    result_url: String,
+) -> BusinessTransferData {
+    BusinessTransferData {
+        Initiator: _initiator,
+        SecurityCredential: security_credential,
+        CommandID: command_id,
+        SenderIdentifierType: sender_identifier_type,
+        RecieverIdentifierType: reciever_identifier_type,
+        Amount: _amount,
+        PartyA: party_a,
+        PartyB: party_b,
+        AccountReference: account_reference,
+        Requester: _requester,
+        Remarks: _remarks,
+        QueueTimeOutURL: queue_time_out_url,
+        ResultURL: result_url,
+    }
+}
+
+pub fn build_transaction_status_data(
+    _initiator: String,
+    security_credential: String,
+    command_id: String,
+    transaction_id: String,
+    original_conversation_id: String,
+    party_a: String,
+    identifier_type: String,
+    result_url: String,
+    queue_time_out_url: String,
+    _remarks: String,
+    _occasion: String,
+) -> TransactionStatusData {
+    TransactionStatusData {
+        Initiator: _initiator,
+        SecurityCredential: security_credential,
+        CommandID: command_id,
+        TransactionID: transaction_id,
+        OriginalConversationID: original_conversation_id,
+        PartyA: party_a,
+        IdentifierType: identifier_type,
+        ResultURL: result_url,
+        QueueTimeOutURL: queue_time_out_url,
+        Remarks: _remarks,
+        Occasion: _occasion,
+    }
+}
+
+pub fn build_account_balance_data(
+    _initiator: String,
+    security_credential: String,
+    command_id: String,
+    party_a: String,
+    identifier_type: String,
+    _remarks: String,
+    queue_time_out_url: String,
+    result_url: String,
+) -> AccountBalanceData {
+    AccountBalanceData {
+        Initiator: _initiator,
+        SecurityCredential: security_credential,
+        CommandID: command_id,
+        PartyA: party_a,
+        IdentifierType: identifier_type,
+        Remarks: _remarks,
+        QueueTimeOutURL: queue_time_out_url,
+        ResultURL: result_url,
+    }
+}
+
+pub fn build_reversal_data(
+    _initiator: String,
+    security_credential: String,
+    command_id: String,
+    transaction_id: String,
+    _amount: u32,
+    receiver_party: String,
+    reciever_identifier_type: String,
+    result_url: String,
+    queue_time_out_url: String,
+    _remarks: String,
+    _occasion: String,
+) -> ReversalData {
+    ReversalData {
+        Initiator: _initiator,
+        SecurityCredential: security_credential,
+        CommandID: command_id,
+        TransactionID: transaction_id,
+        Amount: _amount,
+        ReceiverParty: receiver_party,
+        RecieverIdentifierType: reciever_identifier_type,
+        ResultURL: result_url,
+        QueueTimeOutURL: queue_time_out_url,
+        Remarks: _remarks,
+        Occasion: _occasion,
+    }
+}
+
+pub fn build_dynamic_qr_data(
+    merchant_name: String,
+    ref_no: String,
+    _amount: u32,
+    trx_code: String,
+    _cpi: String,
+    _size: String,
+) -> DynamicQrData {
+    DynamicQrData {
+        MerchantName: merchant_name,
+        RefNo: ref_no,
+        Amount: _amount,
+        TrxCode: trx_code,
+        CPI: _cpi,
+        Size: _size,
+    }
+}
+
+// The QR image is returned as a base64 encoded PNG
+pub fn decode_qr_code(qr_code: &str) -> Result<Vec<u8>, MpesaError> {
+    const PNG_SIGNATURE: [u8; 8] = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+
+    let qr_code: String = qr_code.split_whitespace().collect();
+
+    if qr_code.is_empty() {
+        return Err(MpesaError::validation("qr code", "is empty"));
+    }
+
+    match general_purpose::STANDARD.decode(qr_code) {
+        Ok(image) => {
+            if image.starts_with(&PNG_SIGNATURE) {
+                Ok(image)
+            } else {
+                Err(MpesaError::validation("qr code", "is not a png image"))
+            }
+        }
+        Err(_err) => Err(MpesaError::validation("qr code", "has invalid value")),
+    }
+}
+
+// Accounts are separated by "&" and the fields of each account by "|" i.e.
+// AccountName|Currency|CurrentBalance|AvailableBalance|ReservedAmount|UnclearedBalance
+pub fn parse_account_balance(account_balance: &str) -> Vec<AccountBalanceOutputDetails> {
+    let mut accounts: Vec<AccountBalanceOutputDetails> = Vec::new();
+
+    for account in account_balance.split('&') {
+        if account.trim().is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = account.split('|').map(|x| x.trim()).collect();
+
+        let get_amount = |index: usize| -> f64 {
+            fields
+                .get(index)
+                .and_then(|x| x.parse::<f64>().ok())
+                .unwrap_or(0.0)
+        };
+
+        let account_balance_output_details = AccountBalanceOutputDetails {
+            AccountName: fields.first().unwrap_or(&"").to_string(),
+            Currency: fields.get(1).unwrap_or(&"").to_string(),
+            CurrentBalance: get_amount(2),
+            AvailableBalance: get_amount(3),
+            ReservedAmount: get_amount(4),
+            UnclearedBalance: get_amount(5),
+        };
+
+        accounts.push(account_balance_output_details);
+    }
+
+    accounts
+}
+
 pub fn build_headers(access_token: String) -> HeaderMap {
     let mut headers = HeaderMap::new();
 